@@ -0,0 +1,122 @@
+//! Capture the exact versions of the RustCrypto crates under test.
+//!
+//! Reads the resolved `p256`, `ecdsa`, and `elliptic-curve` packages from
+//! `Cargo.lock`, along with the harness' own git revision, and writes them to
+//! `$OUT_DIR/build_info.rs` for `src/build_info.rs` to include.
+
+use std::{env, fmt::Write as _, fs, path::Path, process::Command};
+
+/// Packages whose resolved version is recorded.
+const PACKAGES: &[&str] = &["p256", "ecdsa", "elliptic-curve"];
+
+/// One `[[package]]` entry from `Cargo.lock`.
+#[derive(Default)]
+struct LockedPackage {
+    name: String,
+    version: String,
+    source: Option<String>,
+    checksum: Option<String>,
+}
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
+    let lock_path = Path::new(&manifest_dir).join("Cargo.lock");
+
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/index");
+
+    let locked = fs::read_to_string(&lock_path)
+        .map(|lock| parse_lock(&lock))
+        .unwrap_or_default();
+
+    let mut out = String::from("pub const DEPENDENCIES: &[Dependency] = &[\n");
+    for &name in PACKAGES {
+        // Several versions of a package may be locked; list each of them.
+        let mut found = false;
+        for package in locked.iter().filter(|p| p.name == name) {
+            let (source, revision) = split_source(package.source.as_deref());
+            found = true;
+            let _ = writeln!(
+                out,
+                "    Dependency {{ name: {:?}, version: {:?}, source: {:?}, revision: {:?}, checksum: {:?} }},",
+                package.name, package.version, source, revision, package.checksum,
+            );
+        }
+        if !found {
+            let _ = writeln!(
+                out,
+                "    Dependency {{ name: {name:?}, version: \"unknown\", source: None, revision: None, checksum: None }},",
+            );
+        }
+    }
+    out.push_str("];\n");
+
+    let _ = writeln!(
+        out,
+        "pub const GIT_REVISION: Option<&str> = {:?};",
+        git(&["rev-parse", "HEAD"])
+    );
+    let _ = writeln!(
+        out,
+        "pub const GIT_DIRTY: bool = {};",
+        git(&["status", "--porcelain", "--untracked-files=no"]).is_some_and(|s| !s.is_empty())
+    );
+
+    fs::write(Path::new(&out_dir).join("build_info.rs"), out)
+        .expect("failed to write build_info.rs");
+}
+
+/// Parse the `[[package]]` tables of a `Cargo.lock` file.
+fn parse_lock(lock: &str) -> Vec<LockedPackage> {
+    let mut packages = Vec::new();
+    let mut current: Option<LockedPackage> = None;
+
+    for line in lock.lines().map(str::trim) {
+        if line.starts_with('[') {
+            packages.extend(current.take());
+            if line == "[[package]]" {
+                current = Some(LockedPackage::default());
+            }
+            continue;
+        }
+
+        let (Some(package), Some((key, value))) = (current.as_mut(), line.split_once('=')) else {
+            continue;
+        };
+        let value = value.trim().trim_matches('"').to_owned();
+
+        match key.trim() {
+            "name" => package.name = value,
+            "version" => package.version = value,
+            "source" => package.source = Some(value),
+            "checksum" => package.checksum = Some(value),
+            _ => (),
+        }
+    }
+
+    packages.extend(current);
+    packages
+}
+
+/// Split a git source such as `git+https://…?rev=…#<sha>` into the
+/// repository and the resolved commit.
+fn split_source(source: Option<&str>) -> (Option<&str>, Option<&str>) {
+    match source {
+        Some(source) if source.starts_with("git+") => match source.rsplit_once('#') {
+            Some((repo, rev)) => (Some(repo), Some(rev)),
+            None => (Some(source), None),
+        },
+        source => (source, None),
+    }
+}
+
+/// Run `git` in the package directory, returning its trimmed output.
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8(output.stdout).ok()?.trim().to_owned())
+}
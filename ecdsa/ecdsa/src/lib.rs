@@ -56,6 +56,8 @@
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 mod recovery;
 
@@ -69,6 +71,8 @@ pub mod hazmat;
 mod signing;
 #[cfg(feature = "verifying")]
 mod verifying;
+#[cfg(feature = "x509")]
+mod x509;
 
 pub use crate::recovery::RecoveryId;
 
@@ -81,7 +85,9 @@ pub use signature::{self, Error, Result, SignatureEncoding};
 #[cfg(feature = "signing")]
 pub use crate::signing::SigningKey;
 #[cfg(feature = "verifying")]
-pub use crate::verifying::VerifyingKey;
+pub use crate::verifying::{PublicKeyExt, VerifyingKey};
+#[cfg(feature = "x509")]
+pub use crate::x509::CertificateError;
 
 use core::{
     fmt::{self, Debug},
@@ -35,6 +35,9 @@ use elliptic_curve::pkcs8::{self, AssociatedOid};
 #[cfg(all(feature = "pem", feature = "serde"))]
 use serdect::serde::{de, ser, Deserialize, Serialize};
 
+#[cfg(feature = "x509")]
+use crate::{x509, CertificateError};
+
 /// ECDSA public key used for verifying signatures. Generic over prime order
 /// elliptic curves (e.g. NIST P-curves)
 ///
@@ -164,6 +167,65 @@ where
     }
 }
 
+/// ECDSA verification directly against an [`elliptic_curve::PublicKey`].
+///
+/// [`PublicKey`] is defined in the `elliptic-curve` crate, which does not
+/// depend on this one, so these methods are provided as an extension trait.
+/// Each one converts the key into a [`VerifyingKey`] and delegates to it.
+pub trait PublicKeyExt<C>
+where
+    C: PrimeCurve,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Verify `signature` against a message [`Digest`] the caller has already
+    /// fed the message into, e.g. while streaming it, so the message is not
+    /// hashed a second time.
+    fn verify_digest<D>(&self, msg_digest: D, signature: &Signature<C>) -> Result<()>
+    where
+        D: Digest;
+
+    /// Check whether this key verifies the signature on the DER-encoded
+    /// X.509 certificate `cert_der`, e.g. to validate a self-signed
+    /// certificate against the key it contains.
+    ///
+    /// The certificate must be signed with `ecdsa-with-SHA256`,
+    /// `-SHA384`, or `-SHA512`; the signature is taken to be over this key's
+    /// curve. Returns `Ok(false)` if the signature doesn't verify, including
+    /// when it isn't a valid DER signature for the curve, and an error if the
+    /// certificate can't be parsed or uses another signature algorithm.
+    ///
+    /// Only the signature is checked: validity periods, extensions, and the
+    /// issuer and subject names are not examined.
+    #[cfg(feature = "x509")]
+    fn verifies_certificate(&self, cert_der: &[u8]) -> core::result::Result<bool, CertificateError>
+    where
+        der::MaxSize<C>: ArrayLength<u8>,
+        <FieldBytesSize<C> as Add>::Output: Add<der::MaxOverhead> + ArrayLength<u8>;
+}
+
+impl<C> PublicKeyExt<C> for PublicKey<C>
+where
+    C: PrimeCurve + CurveArithmetic,
+    AffinePoint<C>: VerifyPrimitive<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    fn verify_digest<D>(&self, msg_digest: D, signature: &Signature<C>) -> Result<()>
+    where
+        D: Digest,
+    {
+        VerifyingKey::from(self).verify_prehash(&msg_digest.finalize(), signature)
+    }
+
+    #[cfg(feature = "x509")]
+    fn verifies_certificate(&self, cert_der: &[u8]) -> core::result::Result<bool, CertificateError>
+    where
+        der::MaxSize<C>: ArrayLength<u8>,
+        <FieldBytesSize<C> as Add>::Output: Add<der::MaxOverhead> + ArrayLength<u8>,
+    {
+        x509::verify_certificate(&VerifyingKey::from(self), cert_der)
+    }
+}
+
 #[cfg(feature = "der")]
 impl<C, D> DigestVerifier<D, der::Signature<C>> for VerifyingKey<C>
 where
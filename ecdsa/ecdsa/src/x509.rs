@@ -0,0 +1,96 @@
+//! Verification of X.509 certificate signatures.
+//!
+//! <https://www.rfc-editor.org/rfc/rfc5280#section-4.1>
+
+use crate::{hazmat::VerifyPrimitive, Signature, SignatureSize, VerifyingKey};
+use core::{fmt, ops::Add};
+use elliptic_curve::{
+    generic_array::ArrayLength,
+    pkcs8::{
+        der::{self, asn1::BitStringRef, Decode, Reader, SliceReader},
+        AlgorithmIdentifierRef, ObjectIdentifier,
+    },
+    AffinePoint, CurveArithmetic, FieldBytesSize, PrimeCurve,
+};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use signature::hazmat::PrehashVerifier;
+
+/// `ecdsa-with-SHA256` (RFC 5758 section 3.2).
+const ECDSA_WITH_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+
+/// `ecdsa-with-SHA384` (RFC 5758 section 3.2).
+const ECDSA_WITH_SHA384: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.3");
+
+/// `ecdsa-with-SHA512` (RFC 5758 section 3.2).
+const ECDSA_WITH_SHA512: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.4");
+
+/// Errors from [`PublicKeyExt::verifies_certificate`][crate::PublicKeyExt::verifies_certificate].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CertificateError {
+    /// The certificate is not valid DER.
+    Der(der::Error),
+
+    /// The certificate is signed with an algorithm other than ECDSA with
+    /// SHA-256, SHA-384, or SHA-512.
+    SignatureAlgorithm(ObjectIdentifier),
+}
+
+impl fmt::Display for CertificateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Der(err) => write!(f, "malformed certificate: {err}"),
+            Self::SignatureAlgorithm(oid) => {
+                write!(f, "certificate signature algorithm {oid} is not ECDSA")
+            }
+        }
+    }
+}
+
+impl From<der::Error> for CertificateError {
+    fn from(err: der::Error) -> Self {
+        Self::Der(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CertificateError {}
+
+/// Check the signature on the DER-encoded X.509 certificate `cert_der`
+/// against `verifying_key`.
+pub(crate) fn verify_certificate<C>(
+    verifying_key: &VerifyingKey<C>,
+    cert_der: &[u8],
+) -> Result<bool, CertificateError>
+where
+    C: PrimeCurve + CurveArithmetic,
+    AffinePoint<C>: VerifyPrimitive<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+    crate::der::MaxSize<C>: ArrayLength<u8>,
+    <FieldBytesSize<C> as Add>::Output: Add<crate::der::MaxOverhead> + ArrayLength<u8>,
+{
+    let mut reader = SliceReader::new(cert_der)?;
+    let (tbs, algorithm, signature) = reader.sequence(|reader| {
+        let tbs = reader.tlv_bytes()?;
+        let algorithm = AlgorithmIdentifierRef::decode(reader)?;
+        let signature = BitStringRef::decode(reader)?;
+        Ok((tbs, algorithm, signature))
+    })?;
+    reader.finish(())?;
+
+    let signature = signature
+        .as_bytes()
+        .ok_or_else(|| der::Tag::BitString.value_error())?;
+    let signature = Signature::<C>::from_der(signature).ok();
+    let verify = |prehash: &[u8]| {
+        signature.as_ref().map_or(false, |signature| {
+            verifying_key.verify_prehash(prehash, signature).is_ok()
+        })
+    };
+
+    match algorithm.oid {
+        ECDSA_WITH_SHA256 => Ok(verify(&Sha256::digest(tbs))),
+        ECDSA_WITH_SHA384 => Ok(verify(&Sha384::digest(tbs))),
+        ECDSA_WITH_SHA512 => Ok(verify(&Sha512::digest(tbs))),
+        oid => Err(CertificateError::SignatureAlgorithm(oid)),
+    }
+}
@@ -15,7 +15,7 @@ use crate::{
     sec1::{CompressedPoint, FromEncodedPoint, ToEncodedPoint},
     subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption},
     zeroize::DefaultIsZeroes,
-    Curve, CurveArithmetic, FieldBytesEncoding, PrimeCurve,
+    Curve, CurveArithmetic, FieldBytesEncoding, FieldModulus, PrimeCurve,
 };
 use core::{
     iter::{Product, Sum},
@@ -31,6 +31,9 @@ use ff::PrimeFieldBits;
 #[cfg(feature = "jwk")]
 use crate::JwkParameters;
 
+#[cfg(feature = "openssh")]
+use crate::OpenSshParameters;
+
 /// Pseudo-coordinate for fixed-based scalar mult output
 pub const PSEUDO_COORDINATE_FIXED_BASE_MUL: [u8; 32] =
     hex!("deadbeef00000000000000000000000000000000000000000000000000000001");
@@ -76,6 +79,11 @@ impl Curve for MockCurve {
 
 impl PrimeCurve for MockCurve {}
 
+impl FieldModulus for MockCurve {
+    const FIELD_MODULUS: U256 =
+        U256::from_be_hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+}
+
 impl CurveArithmetic for MockCurve {
     type AffinePoint = AffinePoint;
     type ProjectivePoint = ProjectivePoint;
@@ -92,6 +100,11 @@ impl JwkParameters for MockCurve {
     const CRV: &'static str = "P-256";
 }
 
+#[cfg(feature = "openssh")]
+impl OpenSshParameters for MockCurve {
+    const SSH_CURVE: &'static str = "nistp256";
+}
+
 /// Example scalar type
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
 pub struct Scalar(ScalarPrimitive);
@@ -22,9 +22,12 @@ use core::{
 use serdect::serde::{de, ser, Deserialize, Serialize};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
+#[cfg(feature = "digest")]
+use digest::{Digest, Output};
+
 #[cfg(feature = "arithmetic")]
 use crate::{
-    public_key::PublicKey,
+    public_key::{DecodeError, FieldModulus, PublicKey},
     sec1::{FromEncodedPoint, ToEncodedPoint},
     AffinePoint, CurveArithmetic,
 };
@@ -117,6 +120,24 @@ impl JwkEcKey {
         PublicKey::from_sec1_bytes(self.to_encoded_point::<C>()?.as_bytes())
     }
 
+    /// Decode a JWK into a [`PublicKey`], reporting why it was rejected.
+    ///
+    /// This accepts exactly the JWKs [`JwkEcKey::to_public_key`] does.
+    #[cfg(feature = "arithmetic")]
+    pub fn try_to_public_key<C>(&self) -> core::result::Result<PublicKey<C>, JwkError>
+    where
+        C: CurveArithmetic + FieldModulus + JwkParameters,
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+        FieldBytesSize<C>: ModulusSize,
+    {
+        let point = self.decode_point::<C>()?;
+        PublicKey::try_from_encoded_point(&point).map_err(|err| match err {
+            DecodeError::NotOnCurve => JwkError::NotOnCurve,
+            DecodeError::Identity => JwkError::Identity,
+            DecodeError::InvalidEncoding => JwkError::Coordinate,
+        })
+    }
+
     /// Create a JWK from a SEC1 [`EncodedPoint`].
     pub fn from_encoded_point<C>(point: &EncodedPoint<C>) -> Option<Self>
     where
@@ -136,16 +157,25 @@ impl JwkEcKey {
 
     /// Get the public key component of this JWK as a SEC1 [`EncodedPoint`].
     pub fn to_encoded_point<C>(&self) -> Result<EncodedPoint<C>>
+    where
+        C: Curve + JwkParameters,
+        FieldBytesSize<C>: ModulusSize,
+    {
+        Ok(self.decode_point::<C>()?)
+    }
+
+    /// Decode the public key component of this JWK, checking its `crv`.
+    fn decode_point<C>(&self) -> core::result::Result<EncodedPoint<C>, JwkError>
     where
         C: Curve + JwkParameters,
         FieldBytesSize<C>: ModulusSize,
     {
         if self.crv != C::CRV {
-            return Err(Error);
+            return Err(JwkError::Curve);
         }
 
-        let x = decode_base64url_fe::<C>(&self.x)?;
-        let y = decode_base64url_fe::<C>(&self.y)?;
+        let x = decode_base64url_fe::<C>(&self.x).map_err(|_| JwkError::Coordinate)?;
+        let y = decode_base64url_fe::<C>(&self.y).map_err(|_| JwkError::Coordinate)?;
         Ok(EncodedPoint::<C>::from_affine_coordinates(&x, &y, false))
     }
 
@@ -158,8 +188,99 @@ impl JwkEcKey {
     {
         self.try_into()
     }
+
+    /// Compute the RFC 7638 JWK thumbprint of this key's public part using
+    /// the hash function `D` (typically SHA-256).
+    ///
+    /// The thumbprint is the digest of the required members `crv`, `kty`,
+    /// `x`, and `y`, serialized in lexicographic order without whitespace.
+    /// The private key parameter `d` is never included.
+    ///
+    /// <https://www.rfc-editor.org/rfc/rfc7638>
+    #[cfg(feature = "digest")]
+    pub fn thumbprint<D: Digest>(&self) -> Output<D> {
+        let value = |s: &str| serde_json::to_string(s).expect("JWK encoding error");
+
+        D::digest(format!(
+            r#"{{"crv":{},"kty":{},"x":{},"y":{}}}"#,
+            value(&self.crv),
+            value(EC_KTY),
+            value(&self.x),
+            value(&self.y)
+        ))
+    }
+}
+
+/// Reasons a JWK does not hold a valid public key, as reported by
+/// [`JwkEcKey::try_to_public_key`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum JwkError {
+    /// The JWK's `crv` is not the requested curve.
+    Curve,
+
+    /// `x` or `y` is not the Base64url encoding of a field element, e.g.
+    /// because it decodes to the wrong number of bytes.
+    Coordinate,
+
+    /// The coordinates are not those of a point on the curve.
+    NotOnCurve,
+
+    /// The point is the identity, which is not a valid public key.
+    Identity,
+}
+
+impl fmt::Display for JwkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Curve => "JWK is for a different curve",
+            Self::Coordinate => "JWK coordinate is not a Base64url-encoded field element",
+            Self::NotOnCurve => "JWK point is not on the curve",
+            Self::Identity => "JWK point is the identity",
+        })
+    }
+}
+
+impl From<JwkError> for Error {
+    fn from(_: JwkError) -> Self {
+        Error
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for JwkError {}
+
+/// Errors from [`PublicKey::from_jwk_with_thumbprint_check`].
+#[cfg(all(feature = "arithmetic", feature = "digest"))]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ThumbprintError {
+    /// The JWK could not be parsed or does not hold a valid public key for
+    /// the requested curve.
+    Jwk(Error),
+
+    /// The JWK parsed correctly, but its thumbprint is not the expected one.
+    Mismatch,
+}
+
+#[cfg(all(feature = "arithmetic", feature = "digest"))]
+impl fmt::Display for ThumbprintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Jwk(err) => write!(f, "invalid JWK: {err}"),
+            Self::Mismatch => f.write_str("JWK thumbprint mismatch"),
+        }
+    }
 }
 
+#[cfg(all(feature = "arithmetic", feature = "digest"))]
+impl From<Error> for ThumbprintError {
+    fn from(err: Error) -> Self {
+        Self::Jwk(err)
+    }
+}
+
+#[cfg(all(feature = "arithmetic", feature = "digest", feature = "std"))]
+impl std::error::Error for ThumbprintError {}
+
 impl FromStr for JwkEcKey {
     type Err = Error;
 
@@ -262,7 +383,7 @@ where
     type Error = Error;
 
     fn try_from(jwk: &JwkEcKey) -> Result<PublicKey<C>> {
-        PublicKey::from_sec1_bytes(jwk.to_encoded_point::<C>()?.as_bytes())
+        jwk.to_public_key::<C>()
     }
 }
 
@@ -556,10 +677,58 @@ impl Serialize for JwkEcKey {
     }
 }
 
-/// Decode a Base64url-encoded field element
+/// Get the `crv` parameter of a JWK without decoding the rest of it, e.g. to
+/// find the curve whose [`JwkParameters::CRV`] it names before parsing it.
+///
+/// Only `crv` is read. Other members are skipped without being validated, so
+/// this succeeding does not mean the JWK will decode as a [`JwkEcKey`].
+/// Returns an error if `jwk` is not a JSON object or has no `crv` member.
+pub fn jwk_crv(jwk: &str) -> Result<String> {
+    /// Visitor which keeps only the `crv` member of a JSON object.
+    struct CrvVisitor;
+
+    impl<'de> de::Visitor<'de> for CrvVisitor {
+        type Value = String;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Formatter::write_str(formatter, "JWK with a crv parameter")
+        }
+
+        fn visit_map<A>(self, mut map: A) -> core::result::Result<String, A::Error>
+        where
+            A: de::MapAccess<'de>,
+        {
+            let mut crv = None;
+
+            while let Some(key) = map.next_key::<String>()? {
+                if key != "crv" {
+                    map.next_value::<de::IgnoredAny>()?;
+                } else if crv.is_none() {
+                    crv = Some(map.next_value()?);
+                } else {
+                    return Err(de::Error::duplicate_field("crv"));
+                }
+            }
+
+            crv.ok_or_else(|| de::Error::missing_field("crv"))
+        }
+    }
+
+    let mut deserializer = serde_json::Deserializer::from_str(jwk);
+    let crv =
+        de::Deserializer::deserialize_map(&mut deserializer, CrvVisitor).map_err(|_| Error)?;
+    deserializer.end().map_err(|_| Error)?;
+    Ok(crv)
+}
+
+/// Decode a Base64url-encoded field element, which must be exactly
+/// `FieldBytes<C>` long.
 fn decode_base64url_fe<C: Curve>(s: &str) -> Result<FieldBytes<C>> {
     let mut result = FieldBytes::<C>::default();
-    Base64Url::decode(s, &mut result).map_err(|_| Error)?;
+    let len = Base64Url::decode(s, &mut result).map_err(|_| Error)?.len();
+    if len != result.len() {
+        return Err(Error);
+    }
     Ok(result)
 }
 
@@ -592,6 +761,18 @@ mod tests {
         }
     "#;
 
+    /// Example public key from RFC 7517 Appendix A.1, without its optional
+    /// `use` and `kid` members.
+    #[cfg(all(feature = "dev", feature = "sha2"))]
+    const RFC7517_PUBLIC_KEY: &str = r#"
+        {
+          "kty":"EC",
+          "crv":"P-256",
+          "x":"MKBCTNIcKUSDii11ySs3526iDZ8AiTo7Tu6KPAqv7D4",
+          "y":"4Etl6SRW2YiLUrN5vfvVHuhp7x8PxltmWWlbbM4IFyM"
+        }
+    "#;
+
     /// Example unsupported JWK (RSA key)
     const UNSUPPORTED_JWK: &str = r#"
         {
@@ -630,6 +811,17 @@ mod tests {
         assert_eq!(jwk.d, None);
     }
 
+    #[test]
+    fn jwk_crv() {
+        assert_eq!(super::jwk_crv(JWK_PUBLIC_KEY).unwrap(), "P-256");
+        assert_eq!(
+            super::jwk_crv(r#"{"kid":"1","crv":"P-384","kty":"EC"}"#).unwrap(),
+            "P-384"
+        );
+        assert_eq!(super::jwk_crv(UNSUPPORTED_JWK), Err(Error));
+        assert_eq!(super::jwk_crv(r#"["crv","P-256"]"#), Err(Error));
+    }
+
     #[test]
     fn parse_unsupported() {
         assert_eq!(JwkEcKey::from_str(UNSUPPORTED_JWK), Err(Error));
@@ -671,4 +863,58 @@ mod tests {
         let jwk2 = JwkEcKey::from_encoded_point::<MockCurve>(&point).unwrap();
         assert_eq!(jwk, jwk2);
     }
+
+    #[cfg(feature = "digest")]
+    #[test]
+    fn thumbprint() {
+        use hex_literal::hex;
+        use sha2::Sha256;
+
+        // The private key parameter must not affect the thumbprint.
+        for jwk in [JWK_PUBLIC_KEY, JWK_PRIVATE_KEY] {
+            let jwk = JwkEcKey::from_str(jwk).unwrap();
+            assert_eq!(
+                jwk.thumbprint::<Sha256>().as_slice(),
+                hex!("fc62b4afa182a09b7dcdcb2c83d95acb8a1b231802ab4e67b624729911c00d25")
+            );
+        }
+    }
+
+    #[cfg(all(feature = "dev", feature = "digest"))]
+    #[test]
+    fn from_jwk_with_thumbprint_check() {
+        use crate::ThumbprintError;
+        use hex_literal::hex;
+        use sha2::Sha256;
+
+        type PublicKey = crate::PublicKey<MockCurve>;
+
+        let thumbprint = hex!("fc62b4afa182a09b7dcdcb2c83d95acb8a1b231802ab4e67b624729911c00d25");
+        let public_key =
+            PublicKey::from_jwk_with_thumbprint_check::<Sha256>(JWK_PUBLIC_KEY, &thumbprint)
+                .unwrap();
+        assert_eq!(public_key, PublicKey::from_jwk_str(JWK_PUBLIC_KEY).unwrap());
+
+        assert_eq!(
+            PublicKey::from_jwk_with_thumbprint_check::<Sha256>(JWK_PUBLIC_KEY, &[0; 32]),
+            Err(ThumbprintError::Mismatch)
+        );
+        assert_eq!(
+            PublicKey::from_jwk_with_thumbprint_check::<Sha256>(UNSUPPORTED_JWK, &thumbprint),
+            Err(ThumbprintError::Jwk(Error))
+        );
+    }
+
+    #[cfg(all(feature = "dev", feature = "sha2"))]
+    #[test]
+    fn to_jwk_thumbprint() {
+        use hex_literal::hex;
+
+        // base64url: cn-I_WNMClehiVp51i_0VpOENW1upEerA8sEam5hn-s
+        let public_key = crate::PublicKey::<MockCurve>::from_jwk_str(RFC7517_PUBLIC_KEY).unwrap();
+        assert_eq!(
+            public_key.to_jwk_thumbprint(),
+            hex!("727f88fd634c0a57a1895a79d62ff4569384356d6ea447ab03cb046a6e619feb")
+        );
+    }
 }
@@ -120,15 +120,36 @@ pub use {
     crate::{
         arithmetic::{CurveArithmetic, PrimeCurveArithmetic},
         point::{AffinePoint, ProjectivePoint},
-        public_key::PublicKey,
+        public_key::{contains_ct, PublicKey, PublicKeyAccumulator},
         scalar::{NonZeroScalar, Scalar},
     },
     ff::{self, Field, PrimeField},
     group::{self, Group},
 };
 
+#[cfg(feature = "ffi")]
+pub use crate::public_key::CPublicKey;
+
+#[cfg(feature = "openssh")]
+pub use crate::public_key::OpenSshParameters;
+
+#[cfg(all(feature = "arithmetic", feature = "sec1"))]
+pub use crate::public_key::{DecodeError, FieldModulus, PointEncodingKind};
+
+#[cfg(all(feature = "arithmetic", feature = "serde"))]
+pub use crate::public_key::sec1_serde;
+
+#[cfg(all(feature = "arithmetic", feature = "pem", feature = "serde"))]
+pub use crate::public_key::pem_serde;
+
+#[cfg(feature = "heapless")]
+pub use crate::public_key::MAX_SEC1_POINT_SIZE;
+
 #[cfg(feature = "jwk")]
-pub use crate::jwk::{JwkEcKey, JwkParameters};
+pub use crate::jwk::{jwk_crv, JwkEcKey, JwkError, JwkParameters};
+
+#[cfg(all(feature = "jwk", feature = "arithmetic", feature = "digest"))]
+pub use crate::jwk::ThumbprintError;
 
 #[cfg(feature = "pkcs8")]
 pub use pkcs8;
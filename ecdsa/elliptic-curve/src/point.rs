@@ -56,6 +56,18 @@ pub trait DecompactPoint<C: Curve>: Sized {
     fn decompact(x: &FieldBytes<C>) -> CtOption<Self>;
 }
 
+/// Decode a point from a backend-specific encoding of its projective
+/// coordinates.
+///
+/// This exists for debugging curve implementations, e.g. inspecting dumps of
+/// intermediate state. The encoding, and even which coordinate system it
+/// uses, is up to the implementation: it is not meant for interoperability.
+pub trait FromProjectiveBytes: Sized {
+    /// Attempt to decode a point, which must lie on the curve (but may be the
+    /// identity).
+    fn from_projective_bytes(bytes: &[u8]) -> CtOption<Self>;
+}
+
 /// Point compression settings.
 pub trait PointCompression {
     /// Should point compression be applied by default?
@@ -1,28 +1,68 @@
 //! Elliptic curve public keys.
 
+mod accumulator;
+#[cfg(feature = "ffi")]
+mod ffi;
+#[cfg(feature = "openssh")]
+mod openssh;
+#[cfg(all(feature = "pem", feature = "serde"))]
+pub mod pem_serde;
+#[cfg(feature = "serde")]
+pub mod sec1_serde;
+
+pub use self::accumulator::PublicKeyAccumulator;
+#[cfg(feature = "ffi")]
+pub use self::ffi::CPublicKey;
+#[cfg(feature = "openssh")]
+pub use self::openssh::OpenSshParameters;
+
 use crate::{
-    point::NonIdentity, AffinePoint, CurveArithmetic, Error, NonZeroScalar, ProjectivePoint, Result,
+    point::{FromProjectiveBytes, NonIdentity},
+    AffinePoint, CurveArithmetic, Error, NonZeroScalar, ProjectivePoint, Result,
 };
 use core::fmt::Debug;
 use group::{Curve as _, Group};
+use subtle::{Choice, ConstantTimeEq};
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
 #[cfg(feature = "alloc")]
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
+
+/// Size of the largest SEC1-encoded point of any curve in the RustCrypto
+/// family: an uncompressed P-521 point (1 + 2 * 66 bytes).
+///
+/// Use it as the capacity for [`PublicKey::to_sec1_heapless`] when the curve
+/// isn't known in advance.
+#[cfg(feature = "heapless")]
+pub const MAX_SEC1_POINT_SIZE: usize = 133;
 
 #[cfg(feature = "jwk")]
 use crate::{JwkEcKey, JwkParameters};
 
+#[cfg(all(feature = "jwk", feature = "digest"))]
+use {crate::ThumbprintError, digest::Digest};
+
 #[cfg(feature = "pem")]
 use core::str::FromStr;
 
 #[cfg(feature = "sec1")]
 use {
     crate::{
-        point::PointCompression,
-        sec1::{CompressedPoint, EncodedPoint, FromEncodedPoint, ModulusSize, ToEncodedPoint},
-        Curve, FieldBytesSize,
+        point::{AffineCoordinates, PointCompression},
+        sec1::{
+            CompressedPoint, Coordinates, EncodedPoint, FromEncodedPoint, ModulusSize, Tag,
+            ToEncodedPoint,
+        },
+        Curve, FieldBytes, FieldBytesEncoding, FieldBytesSize,
+    },
+    base16ct::HexDisplay,
+    core::{
+        cmp::Ordering,
+        fmt,
+        hash::{Hash, Hasher},
     },
-    core::cmp::Ordering,
     subtle::CtOption,
 };
 
@@ -35,7 +75,7 @@ use alloc::string::{String, ToString};
 #[cfg(feature = "serde")]
 use serdect::serde::{de, ser, Deserialize, Serialize};
 
-#[cfg(any(feature = "pem", feature = "serde"))]
+#[cfg(feature = "pkcs8")]
 use pkcs8::DecodePublicKey;
 
 #[cfg(all(feature = "sec1", feature = "pkcs8"))]
@@ -47,6 +87,71 @@ use {
     pkcs8::der,
 };
 
+/// SEC1 point encodings, as reported by
+/// [`PublicKey::from_sec1_bytes_reporting`].
+#[cfg(feature = "sec1")]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum PointEncodingKind {
+    /// `0x02` or `0x03` followed by the x-coordinate.
+    Compressed,
+
+    /// `0x04` followed by the x- and y-coordinates.
+    Uncompressed,
+
+    /// `0x06` or `0x07` (the y-coordinate's parity, as for compressed points)
+    /// followed by the x- and y-coordinates (ANSI X9.62).
+    Hybrid,
+
+    /// `0x05` followed by the x-coordinate of a compactly represented point.
+    Compact,
+}
+
+/// Reasons [`PublicKey::try_from_encoded_point`] rejects a point.
+#[cfg(feature = "sec1")]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum DecodeError {
+    /// The coordinates are valid field elements, but not those of a point on
+    /// the curve.
+    NotOnCurve,
+
+    /// The point is the identity, which is not a valid public key.
+    Identity,
+
+    /// A coordinate is not the canonical encoding of a field element, i.e. it
+    /// is not less than the field modulus.
+    InvalidEncoding,
+}
+
+#[cfg(feature = "sec1")]
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::NotOnCurve => "point is not on the curve",
+            Self::Identity => "point is the identity",
+            Self::InvalidEncoding => "point coordinate is not a valid field element",
+        })
+    }
+}
+
+#[cfg(feature = "sec1")]
+impl From<DecodeError> for Error {
+    fn from(_: DecodeError) -> Self {
+        Error
+    }
+}
+
+#[cfg(all(feature = "sec1", feature = "std"))]
+impl std::error::Error for DecodeError {}
+
+/// Base field modulus of an elliptic curve, which
+/// [`PublicKey::try_from_encoded_point`] uses to tell coordinates which aren't
+/// field elements apart from points which aren't on the curve.
+#[cfg(feature = "sec1")]
+pub trait FieldModulus: Curve {
+    /// Modulus 𝑝 of the field the curve's coordinates are defined over.
+    const FIELD_MODULUS: Self::Uint;
+}
+
 /// Elliptic curve public keys.
 ///
 /// This is a wrapper type for [`AffinePoint`] which ensures an inner
@@ -83,6 +188,16 @@ use {
 /// Subject Public Key Info (SPKI) as the encoding format.
 ///
 /// For a more text-friendly encoding of public keys, use [`JwkEcKey`] instead.
+///
+/// # Constant-time comparison
+///
+/// `==` defers to the [`PartialEq`] impl of the curve's [`AffinePoint`],
+/// so whether it runs in constant time depends on the curve implementation.
+/// Curves built on `primeorder`, such as `p256`, compare in constant time,
+/// but nothing here guarantees it. Where timing matters, compare keys with
+/// [`ConstantTimeEq::ct_eq`] instead, which always uses the point's own
+/// constant-time comparison, and combine the resulting [`Choice`]s without
+/// branching.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PublicKey<C>
 where
@@ -113,6 +228,141 @@ where
         }
     }
 
+    /// Is this [`PublicKey`] equal to `k×G`, i.e. the public key which
+    /// corresponds to the secret scalar `k`?
+    ///
+    /// The fixed-base scalar multiplication and the point comparison are
+    /// performed in constant time (provided the curve implementation's
+    /// arithmetic is constant time), so no intermediate values leak through
+    /// timing. Only the final boolean result is variable-time.
+    pub fn is_generator_multiple(&self, k: &NonZeroScalar<C>) -> bool {
+        Self::from_secret_scalar(k).point.ct_eq(&self.point).into()
+    }
+
+    /// Do `self` and `other` wrap the same [`AffinePoint`]?
+    ///
+    /// This compares the points directly rather than any encoding of them,
+    /// so e.g. the compressed and uncompressed SEC1 encodings of a key decode
+    /// to equal keys. It is exactly the comparison performed by `==`, spelled
+    /// out for code which wants to be explicit about it; see the
+    /// [constant-time comparison](PublicKey#constant-time-comparison) notes.
+    pub fn point_eq(&self, other: &Self) -> bool {
+        self.point == other.point
+    }
+
+    /// Re-check that the wrapped [`AffinePoint`] is on the curve and is not
+    /// the identity, the invariants every constructor of [`PublicKey`]
+    /// enforces.
+    ///
+    /// This is for defense in depth where a key's memory may not have
+    /// come from a constructor, e.g. after FFI interop or an unchecked
+    /// deserialization path. The point is re-encoded uncompressed and decoded
+    /// again, so the check is whatever the curve's [`FromEncodedPoint`] impl
+    /// validates.
+    #[cfg(feature = "sec1")]
+    pub fn verify_on_curve(&self) -> bool
+    where
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+        FieldBytesSize<C>: ModulusSize,
+    {
+        if ProjectivePoint::<C>::from(self.point).is_identity().into() {
+            return false;
+        }
+
+        let encoded = self.point.to_encoded_point(false);
+        Option::from(AffinePoint::<C>::from_encoded_point(&encoded)) == Some(self.point)
+    }
+
+    /// Negate this [`PublicKey`], i.e. compute `-P`: the point with the same
+    /// x-coordinate and the other y-coordinate.
+    ///
+    /// `-P` is the public key of the negated secret scalar, and like `P` is
+    /// on the curve and never the identity.
+    ///
+    /// In ECDSA public key recovery, the two candidates for the ephemeral
+    /// point `R` which share the x-coordinate `r` are negations of each other;
+    /// the recovery ID's y-parity bit selects between them. Note the
+    /// *recovered keys* are not negations of each other in general: recovering
+    /// `(r, s)` with `-R` in place of `R` yields `-Q - 2r⁻¹z×G`, which equals
+    /// `-Q` only if the message hash `z` is zero.
+    pub fn negate(&self) -> Self {
+        // The negation of a non-identity point is never the identity
+        Self {
+            point: (-self.to_projective()).to_affine(),
+        }
+    }
+
+    /// Add two public keys, i.e. compute `P + Q`, e.g. to aggregate the key
+    /// shares of a threshold or multi-party protocol.
+    ///
+    /// `P + Q` is the public key of the sum of the two secret scalars.
+    /// Returns an error if the sum is the identity, i.e. if `Q = -P`.
+    pub fn combine(&self, other: &PublicKey<C>) -> Result<PublicKey<C>> {
+        Self::from_affine((self.to_projective() + other.to_projective()).to_affine())
+    }
+
+    /// Multiply this public key by a scalar, i.e. compute `k×P`, e.g. to
+    /// blind or tweak a key, or as the shared point of an ECDH exchange.
+    ///
+    /// `k×P` is the public key of the product of `k` and the secret scalar.
+    pub fn mul_scalar(&self, scalar: &NonZeroScalar<C>) -> PublicKey<C> {
+        // A non-identity point of prime order times a non-zero scalar is
+        // never the identity
+        Self {
+            point: (self.to_projective() * scalar.as_ref()).to_affine(),
+        }
+    }
+
+    /// Compute the shared point of an elliptic curve Diffie-Hellman exchange
+    /// between `secret` and the peer's `public` key, i.e. `secret×public`.
+    ///
+    /// Both parties arrive at the same point: the product of their two secret
+    /// scalars times the generator. Like [`PublicKey::mul_scalar`] it is
+    /// never the identity. This is the raw shared point; derive keys from
+    /// its x-coordinate with a KDF rather than using it directly, as
+    /// the `ecdh` module does.
+    pub fn diffie_hellman(
+        secret: &NonZeroScalar<C>,
+        public: &PublicKey<C>,
+    ) -> NonIdentity<AffinePoint<C>> {
+        public.mul_scalar(secret).to_nonidentity()
+    }
+
+    /// Compute the Pedersen commitment `value×G + blinding×H` to `value`,
+    /// where `H` is a second generator given as a [`PublicKey`].
+    ///
+    /// The commitment only hides and binds `value` if nobody knows the
+    /// discrete logarithm of `H` with respect to `G`, so `H` should be derived
+    /// e.g. by hashing to the curve rather than from a known scalar.
+    ///
+    /// Returns an error if the result is the identity.
+    pub fn commit(
+        value: &NonZeroScalar<C>,
+        blinding: &NonZeroScalar<C>,
+        h: &PublicKey<C>,
+    ) -> Result<Self> {
+        let point = C::ProjectivePoint::generator() * value.as_ref()
+            + h.to_projective() * blinding.as_ref();
+        Self::from_affine(point.to_affine())
+    }
+
+    /// Decode a [`PublicKey`] from the projective coordinates of a point, in
+    /// the curve implementation's [`FromProjectiveBytes`] encoding, and
+    /// normalize it to affine.
+    ///
+    /// The encoding is backend-specific and intended only for inspecting
+    /// intermediate values while debugging, not for interoperability. Returns
+    /// an error if the coordinates don't form a valid point or the point is
+    /// the identity.
+    pub fn from_projective_bytes(bytes: &[u8]) -> Result<Self>
+    where
+        ProjectivePoint<C>: FromProjectiveBytes,
+    {
+        Option::<ProjectivePoint<C>>::from(ProjectivePoint::<C>::from_projective_bytes(bytes))
+            .ok_or(Error)
+            .and_then(|point| Self::from_affine(point.to_affine()))
+    }
+
     /// Decode [`PublicKey`] (compressed or uncompressed) from the
     /// `Elliptic-Curve-Point-to-Octet-String` encoding described in
     /// SEC 1: Elliptic Curve Cryptography (Version 2.0) section
@@ -130,6 +380,174 @@ where
         Option::from(Self::from_encoded_point(&point)).ok_or(Error)
     }
 
+    /// Decode each of `items` with [`PublicKey::from_sec1_bytes`]
+    /// independently, so a malformed key only fails its own entry of the
+    /// result, which has the same length and order as `items`.
+    #[cfg(all(feature = "alloc", feature = "sec1"))]
+    pub fn from_sec1_bytes_batch(items: &[&[u8]]) -> Vec<Result<Self>>
+    where
+        C: Curve,
+        FieldBytesSize<C>: ModulusSize,
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    {
+        items
+            .iter()
+            .map(|bytes| Self::from_sec1_bytes(bytes))
+            .collect()
+    }
+
+    /// Do the SEC1 encodings `a` and `b` encode the same key?
+    ///
+    /// Both are decoded with [`PublicKey::from_sec1_bytes`] and the points
+    /// compared, so the compressed and uncompressed encodings of a key are
+    /// equal even though their bytes differ. Returns an error if either
+    /// encoding fails to decode.
+    #[cfg(feature = "sec1")]
+    pub fn eq_bytes(a: &[u8], b: &[u8]) -> Result<bool>
+    where
+        C: Curve,
+        FieldBytesSize<C>: ModulusSize,
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    {
+        Ok(Self::from_sec1_bytes(a)? == Self::from_sec1_bytes(b)?)
+    }
+
+    /// Build a [`PublicKey`] from its big-endian affine coordinates, e.g. as
+    /// returned separately by a KMS, without assembling a SEC1 encoding by
+    /// hand.
+    ///
+    /// Fails if either coordinate isn't a canonical field element or the
+    /// point isn't on the curve. The identity has no affine coordinates, so
+    /// it can never be produced.
+    #[cfg(feature = "sec1")]
+    pub fn from_coordinates(x: &FieldBytes<C>, y: &FieldBytes<C>) -> Result<Self>
+    where
+        C: Curve,
+        FieldBytesSize<C>: ModulusSize,
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    {
+        let point = EncodedPoint::<C>::from_affine_coordinates(x, y, false);
+        Option::from(Self::from_encoded_point(&point)).ok_or(Error)
+    }
+
+    /// Decode a [`PublicKey`] as [`PublicKey::from_sec1_bytes`] does, also
+    /// reporting which [`PointEncodingKind`] the input used, e.g. for audit
+    /// logs recording how a key was transmitted.
+    ///
+    /// Unlike [`PublicKey::from_sec1_bytes`], this also accepts the hybrid
+    /// encoding, whose tag must agree with the parity of the y-coordinate.
+    #[cfg(feature = "sec1")]
+    pub fn from_sec1_bytes_reporting(bytes: &[u8]) -> Result<(Self, PointEncodingKind)>
+    where
+        C: Curve,
+        FieldBytesSize<C>: ModulusSize,
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    {
+        let tag = *bytes.first().ok_or(Error)?;
+
+        if let 0x06 | 0x07 = tag {
+            let size = FieldBytes::<C>::default().len();
+            if bytes.len() != 1 + 2 * size {
+                return Err(Error);
+            }
+
+            let x = FieldBytes::<C>::from_slice(&bytes[1..=size]);
+            let y = FieldBytes::<C>::from_slice(&bytes[1 + size..]);
+            let point = EncodedPoint::<C>::from_affine_coordinates(x, y, false);
+            let public_key = Option::<Self>::from(Self::from_encoded_point(&point)).ok_or(Error)?;
+
+            return if bool::from(public_key.point.y_is_odd()) == (tag == 0x07) {
+                Ok((public_key, PointEncodingKind::Hybrid))
+            } else {
+                Err(Error)
+            };
+        }
+
+        let kind = match Tag::from_u8(tag).map_err(|_| Error)? {
+            Tag::CompressedEvenY | Tag::CompressedOddY => PointEncodingKind::Compressed,
+            Tag::Uncompressed => PointEncodingKind::Uncompressed,
+            Tag::Compact => PointEncodingKind::Compact,
+            Tag::Identity => return Err(Error),
+        };
+
+        Self::from_sec1_bytes(bytes).map(|public_key| (public_key, kind))
+    }
+
+    /// Check that an untrusted SEC1-encoded point lies on the curve `C`,
+    /// rejecting points on its quadratic twist (or any other curve).
+    ///
+    /// Feeding an off-curve point to scalar multiplication is the basis of
+    /// invalid-curve attacks, which can leak a secret scalar a few bits at a
+    /// time over many queries. Every [`PublicKey`] constructor already
+    /// performs this check, so this is the same validation
+    /// [`PublicKey::from_encoded_point`] does, written out for protocols which
+    /// want to check a peer's point explicitly.
+    ///
+    /// Uncompressed points must satisfy the curve equation. Compressed points
+    /// are rejected if their x-coordinate has no square root on `C`, which is
+    /// exactly the case for x-coordinates belonging to the twist.
+    #[cfg(feature = "sec1")]
+    pub fn reject_twist_points(point: &EncodedPoint<C>) -> Result<()>
+    where
+        C: Curve,
+        FieldBytesSize<C>: ModulusSize,
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    {
+        Option::<Self>::from(Self::from_encoded_point(point))
+            .map(|_| ())
+            .ok_or(Error)
+    }
+
+    /// Initialize [`PublicKey`] from an [`EncodedPoint`], reporting why the
+    /// point was rejected.
+    ///
+    /// This accepts exactly the points [`FromEncodedPoint::from_encoded_point`]
+    /// does, but is not constant-time: use it for diagnostics when parsing
+    /// untrusted input, and the [`FromEncodedPoint`] impl where timing
+    /// matters.
+    #[cfg(feature = "sec1")]
+    pub fn try_from_encoded_point(
+        encoded_point: &EncodedPoint<C>,
+    ) -> core::result::Result<Self, DecodeError>
+    where
+        C: FieldModulus,
+        FieldBytesSize<C>: ModulusSize,
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    {
+        if encoded_point.is_identity() {
+            return Err(DecodeError::Identity);
+        }
+
+        match Option::from(AffinePoint::<C>::from_encoded_point(encoded_point)) {
+            Some(point) => Self::from_affine(point).map_err(|_| DecodeError::Identity),
+            None => Err(Self::classify_rejected(encoded_point)),
+        }
+    }
+
+    /// Say why [`FromEncodedPoint::from_encoded_point`] rejected a
+    /// non-identity point: either a coordinate is not less than 𝑝, or the
+    /// coordinates aren't those of a point on the curve.
+    #[cfg(feature = "sec1")]
+    fn classify_rejected(encoded_point: &EncodedPoint<C>) -> DecodeError
+    where
+        C: FieldModulus,
+        FieldBytesSize<C>: ModulusSize,
+    {
+        let in_field =
+            |bytes: &FieldBytes<C>| C::Uint::decode_field_bytes(bytes) < C::FIELD_MODULUS;
+        let canonical = match encoded_point.coordinates() {
+            Coordinates::Identity => true,
+            Coordinates::Compact { x } | Coordinates::Compressed { x, .. } => in_field(x),
+            Coordinates::Uncompressed { x, y } => in_field(x) && in_field(y),
+        };
+
+        if canonical {
+            DecodeError::NotOnCurve
+        } else {
+            DecodeError::InvalidEncoding
+        }
+    }
+
     /// Convert this [`PublicKey`] into the
     /// `Elliptic-Curve-Point-to-Octet-String` encoding described in
     /// SEC 1: Elliptic Curve Cryptography (Version 2.0) section 2.3.3
@@ -146,6 +564,259 @@ where
         EncodedPoint::<C>::from(self).to_bytes()
     }
 
+    /// Convert this [`PublicKey`] into the SEC1
+    /// `Elliptic-Curve-Point-to-Octet-String` encoding, as
+    /// [`PublicKey::to_sec1_bytes`] does, but into a fixed-capacity
+    /// [`heapless::Vec`] rather than a heap allocation.
+    ///
+    /// Returns an error if `N` is too small for the encoded point.
+    /// [`MAX_SEC1_POINT_SIZE`] is large enough for any curve.
+    #[cfg(feature = "heapless")]
+    pub fn to_sec1_heapless<const N: usize>(&self) -> Result<heapless::Vec<u8, N>>
+    where
+        C: CurveArithmetic + PointCompression,
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+        FieldBytesSize<C>: ModulusSize,
+    {
+        heapless::Vec::from_slice(EncodedPoint::<C>::from(self).as_bytes()).map_err(|_| Error)
+    }
+
+    /// Encode this [`PublicKey`] as a TLS 1.3 `UncompressedPointRepresentation`
+    /// (RFC 8446 section 4.2.8.2): the `0x04` `legacy_form` byte followed by
+    /// the big-endian affine x- and y-coordinates.
+    ///
+    /// This is the key share format used for ECDHE with the NIST curves.
+    ///
+    /// <https://www.rfc-editor.org/rfc/rfc8446#section-4.2.8.2>
+    #[cfg(all(feature = "alloc", feature = "sec1"))]
+    pub fn to_tls_point(&self) -> Vec<u8>
+    where
+        C: Curve,
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+        FieldBytesSize<C>: ModulusSize,
+    {
+        self.to_encoded_point(false).as_bytes().to_vec()
+    }
+
+    /// Decode a [`PublicKey`] from a TLS 1.3 `UncompressedPointRepresentation`
+    /// (RFC 8446 section 4.2.8.2).
+    ///
+    /// Unlike [`PublicKey::from_sec1_bytes`], only the uncompressed form is
+    /// accepted: RFC 8446 requires peers to reject compressed points and the
+    /// identity, which are "malformed" `KeyShareEntry` values.
+    ///
+    /// <https://www.rfc-editor.org/rfc/rfc8446#section-4.2.8.2>
+    #[cfg(all(feature = "alloc", feature = "sec1"))]
+    pub fn from_tls_point(bytes: &[u8]) -> Result<Self>
+    where
+        C: Curve,
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+        FieldBytesSize<C>: ModulusSize,
+    {
+        let point = EncodedPoint::<C>::from_bytes(bytes).map_err(|_| Error)?;
+
+        if point.tag() != Tag::Uncompressed {
+            return Err(Error);
+        }
+
+        Option::from(Self::from_encoded_point(&point)).ok_or(Error)
+    }
+
+    /// Encode this [`PublicKey`] as its SEC1 encoding, optionally compressed,
+    /// prefixed with a single byte holding the encoding's length.
+    ///
+    /// Keys written this way can be concatenated and read back one at a time
+    /// with [`PublicKey::from_wire`].
+    #[cfg(all(feature = "alloc", feature = "sec1"))]
+    pub fn to_wire(&self, compress: bool) -> Vec<u8>
+    where
+        C: Curve,
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+        FieldBytesSize<C>: ModulusSize,
+    {
+        let point = self.to_encoded_point(compress);
+        // The largest SEC1 point, an uncompressed P-521 one, is 133 bytes
+        let len = u8::try_from(point.len()).expect("SEC1 point too long");
+
+        let mut wire = Vec::with_capacity(1 + point.len());
+        wire.push(len);
+        wire.extend_from_slice(point.as_bytes());
+        wire
+    }
+
+    /// Decode a [`PublicKey`] written by [`PublicKey::to_wire`] from the front
+    /// of `bytes`, returning it along with the bytes that follow it.
+    ///
+    /// The SEC1 encoding may be compressed or uncompressed. Returns an error
+    /// if `bytes` is shorter than its length prefix claims, or if the
+    /// encoding isn't a valid point as [`PublicKey::from_sec1_bytes`] decides.
+    #[cfg(feature = "sec1")]
+    pub fn from_wire(bytes: &[u8]) -> Result<(Self, &[u8])>
+    where
+        C: Curve,
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+        FieldBytesSize<C>: ModulusSize,
+    {
+        let (&len, rest) = bytes.split_first().ok_or(Error)?;
+        let len = usize::from(len);
+        if rest.len() < len {
+            return Err(Error);
+        }
+
+        let (point, rest) = rest.split_at(len);
+        Ok((Self::from_sec1_bytes(point)?, rest))
+    }
+
+    /// Encode this [`PublicKey`] in the layout `ring` expects of the bytes
+    /// passed to `ring::signature::UnparsedPublicKey` and
+    /// `ring::agreement::UnparsedPublicKey` for the ECDSA and ECDH
+    /// algorithms: the `0x04` tag followed by the big-endian affine x- and
+    /// y-coordinates.
+    ///
+    /// This is the uncompressed SEC1 encoding; the separately named helper
+    /// exists to document that compatibility for code migrating from `ring`.
+    #[cfg(feature = "ring-interop")]
+    pub fn to_ring_uncompressed(&self) -> Vec<u8>
+    where
+        C: Curve,
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+        FieldBytesSize<C>: ModulusSize,
+    {
+        self.to_encoded_point(false).as_bytes().to_vec()
+    }
+
+    /// Decode a [`PublicKey`] from the bytes of a `ring` `UnparsedPublicKey`,
+    /// applying the same checks `ring` does when it parses one: the input
+    /// must be an uncompressed point of exactly `1 + 2 * field size` bytes
+    /// whose coordinates are canonical and lie on the curve.
+    ///
+    /// Compressed points, which `ring` does not accept, are rejected.
+    #[cfg(feature = "ring-interop")]
+    pub fn from_ring_uncompressed(bytes: &[u8]) -> Result<Self>
+    where
+        C: Curve,
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+        FieldBytesSize<C>: ModulusSize,
+    {
+        let point = EncodedPoint::<C>::from_bytes(bytes).map_err(|_| Error)?;
+
+        if point.tag() != Tag::Uncompressed {
+            return Err(Error);
+        }
+
+        Option::from(Self::from_encoded_point(&point)).ok_or(Error)
+    }
+
+    /// Decode a [`PublicKey`] from the two parts of its compressed SEC1
+    /// encoding: the tag byte and the affine x-coordinate.
+    ///
+    /// This is the inverse of [`PublicKey::to_tagged_parts`]. Tags other than
+    /// `0x02` and `0x03` are rejected.
+    #[cfg(feature = "sec1")]
+    pub fn from_tagged_parts(tag: u8, x: &FieldBytes<C>) -> Result<Self>
+    where
+        C: Curve,
+        FieldBytesSize<C>: ModulusSize,
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    {
+        match Tag::from_u8(tag) {
+            Ok(Tag::CompressedEvenY | Tag::CompressedOddY) => (),
+            _ => return Err(Error),
+        }
+
+        let mut bytes = CompressedPoint::<C>::default();
+        bytes[0] = tag;
+        bytes[1..].copy_from_slice(x);
+        Self::from_sec1_bytes(&bytes)
+    }
+
+    /// Split this [`PublicKey`] into the two parts of its compressed SEC1
+    /// encoding: the tag byte (`0x02` if the affine y-coordinate is even,
+    /// `0x03` if it's odd) and the affine x-coordinate.
+    #[cfg(feature = "sec1")]
+    pub fn to_tagged_parts(&self) -> (u8, FieldBytes<C>) {
+        let tag = if self.point.y_is_odd().into() {
+            Tag::CompressedOddY
+        } else {
+            Tag::CompressedEvenY
+        };
+
+        (tag.into(), self.point.x())
+    }
+
+    /// Encode this [`PublicKey`] as a compressed SEC1 point in a fixed-size
+    /// array, without allocating.
+    ///
+    /// Unlike [`PublicKey::to_sec1_bytes`], the output is always compressed,
+    /// regardless of the curve's [`PointCompression`] setting.
+    #[cfg(feature = "sec1")]
+    pub fn to_bytes(&self) -> CompressedPoint<C>
+    where
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+        FieldBytesSize<C>: ModulusSize,
+    {
+        CompressedPoint::<C>::clone_from_slice(self.to_encoded_point(true).as_bytes())
+    }
+
+    /// Compressed SEC1 encoding of this [`PublicKey`]: its x-coordinate and
+    /// the parity of its y-coordinate.
+    ///
+    /// The same as [`PublicKey::to_bytes`]; see [`PublicKey::decompress`]
+    /// for the uncompressed form.
+    #[cfg(feature = "sec1")]
+    pub fn compress(&self) -> CompressedPoint<C>
+    where
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+        FieldBytesSize<C>: ModulusSize,
+    {
+        self.to_bytes()
+    }
+
+    /// Uncompressed SEC1 encoding of this [`PublicKey`], with both affine
+    /// coordinates.
+    ///
+    /// The same as `to_encoded_point(false)`, so a key decoded from a
+    /// compressed point can be re-encoded with its y-coordinate recovered.
+    #[cfg(feature = "sec1")]
+    pub fn decompress(&self) -> EncodedPoint<C>
+    where
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+        FieldBytesSize<C>: ModulusSize,
+    {
+        self.to_encoded_point(false)
+    }
+
+    /// Big-endian affine x-coordinate of this [`PublicKey`]: bytes `1..=n`
+    /// of its uncompressed SEC1 encoding, where `n` is the field size.
+    ///
+    /// The inverse of [`PublicKey::x`] and [`PublicKey::y`] is
+    /// [`PublicKey::from_coordinates`].
+    #[cfg(feature = "sec1")]
+    pub fn x(&self) -> FieldBytes<C>
+    where
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+        FieldBytesSize<C>: ModulusSize,
+    {
+        self.to_encoded_point(false)
+            .x()
+            .cloned()
+            .expect("public keys are never the identity")
+    }
+
+    /// Big-endian affine y-coordinate of this [`PublicKey`]: the final `n`
+    /// bytes of its uncompressed SEC1 encoding, where `n` is the field size.
+    #[cfg(feature = "sec1")]
+    pub fn y(&self) -> FieldBytes<C>
+    where
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+        FieldBytesSize<C>: ModulusSize,
+    {
+        self.to_encoded_point(false)
+            .y()
+            .cloned()
+            .expect("public keys are never the identity")
+    }
+
     /// Borrow the inner [`AffinePoint`] from this [`PublicKey`].
     ///
     /// In ECC, public keys are elliptic curve points.
@@ -185,6 +856,34 @@ where
         jwk.parse::<JwkEcKey>().and_then(|jwk| Self::from_jwk(&jwk))
     }
 
+    /// Parse a string containing a JSON Web Key (JWK) into a [`PublicKey`],
+    /// accepting it only if its RFC 7638 thumbprint computed with `D` equals
+    /// `expected_thumbprint`.
+    ///
+    /// Binding acceptance to a thumbprint obtained out of band prevents an
+    /// attacker from substituting a different key. A JWK which parses but
+    /// has the wrong thumbprint is reported as [`ThumbprintError::Mismatch`],
+    /// distinct from a malformed JWK.
+    #[cfg(all(feature = "jwk", feature = "digest"))]
+    pub fn from_jwk_with_thumbprint_check<D>(
+        jwk: &str,
+        expected_thumbprint: &[u8],
+    ) -> core::result::Result<Self, ThumbprintError>
+    where
+        C: Curve + JwkParameters,
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+        FieldBytesSize<C>: ModulusSize,
+        D: Digest,
+    {
+        let jwk = jwk.parse::<JwkEcKey>()?;
+
+        if jwk.thumbprint::<D>().as_slice() != expected_thumbprint {
+            return Err(ThumbprintError::Mismatch);
+        }
+
+        Ok(Self::from_jwk(&jwk)?)
+    }
+
     /// Serialize this public key as [`JwkEcKey`] JSON Web Key (JWK).
     #[cfg(feature = "jwk")]
     pub fn to_jwk(&self) -> JwkEcKey
@@ -206,6 +905,38 @@ where
     {
         self.to_jwk().to_string()
     }
+
+    /// Compute the RFC 7638 SHA-256 thumbprint of this key's JWK, e.g. to
+    /// derive a JWT `kid`.
+    ///
+    /// This is [`JwkEcKey::thumbprint`] of [`PublicKey::to_jwk`] using
+    /// SHA-256.
+    #[cfg(all(feature = "jwk", feature = "sha2"))]
+    pub fn to_jwk_thumbprint(&self) -> [u8; 32]
+    where
+        C: Curve + JwkParameters,
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+        FieldBytesSize<C>: ModulusSize,
+    {
+        self.to_jwk().thumbprint::<sha2::Sha256>().into()
+    }
+
+    /// Does `jwk` hold this public key?
+    ///
+    /// Compares the JWK's `crv`, `x`, and `y` parameters against this key
+    /// directly, without validating the JWK's point or constructing a
+    /// [`PublicKey`] from it. Returns `false` rather than an error if the JWK
+    /// is for a different curve or its coordinates are malformed.
+    #[cfg(feature = "jwk")]
+    pub fn matches_jwk(&self, jwk: &JwkEcKey) -> bool
+    where
+        C: Curve + JwkParameters,
+        AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+        FieldBytesSize<C>: ModulusSize,
+    {
+        jwk.to_encoded_point::<C>()
+            .map_or(false, |point| point == self.to_encoded_point(false))
+    }
 }
 
 impl<C> AsRef<AffinePoint<C>> for PublicKey<C>
@@ -219,6 +950,48 @@ where
 
 impl<C> Copy for PublicKey<C> where C: CurveArithmetic {}
 
+/// Erases the key's point and overwrites it with the curve's generator, for
+/// contexts which require all key material to be erased. The zeroized value
+/// is still a valid [`PublicKey`], just not the original one.
+///
+/// There is no `ZeroizeOnDrop` impl: [`PublicKey`] is [`Copy`], so it can't
+/// implement [`Drop`], and copies would escape erasure regardless.
+#[cfg(feature = "zeroize")]
+impl<C> Zeroize for PublicKey<C>
+where
+    C: CurveArithmetic,
+{
+    fn zeroize(&mut self) {
+        // The zeroed point is the identity, which a public key never is
+        self.point.zeroize();
+        self.point = ProjectivePoint::<C>::generator().to_affine();
+    }
+}
+
+impl<C> ConstantTimeEq for PublicKey<C>
+where
+    C: CurveArithmetic,
+{
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.point.ct_eq(&other.point)
+    }
+}
+
+/// Is `key` one of the entries of `set`?
+///
+/// Every entry is compared using [`ConstantTimeEq`] and the results are
+/// combined without branching, so this always scans the full set: the time
+/// taken depends only on `set.len()`, not on whether or where `key` is found.
+/// This makes it suitable for allowlist or revocation checks where the
+/// position of a match must not leak.
+pub fn contains_ct<C>(set: &[PublicKey<C>], key: &PublicKey<C>) -> Choice
+where
+    C: CurveArithmetic,
+{
+    set.iter()
+        .fold(Choice::from(0), |found, entry| found | entry.ct_eq(key))
+}
+
 #[cfg(feature = "sec1")]
 impl<C> FromEncodedPoint<C> for PublicKey<C>
 where
@@ -364,6 +1137,48 @@ where
     }
 }
 
+#[cfg(feature = "sec1")]
+impl<C> Hash for PublicKey<C>
+where
+    C: CurveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hash the same encoding `Ord` compares, which is unique per point
+        self.to_encoded_point(false).as_bytes().hash(state);
+    }
+}
+
+/// Writes the compressed SEC1 encoding in lowercase hex.
+///
+/// There is no `Display` impl, since with the `pem` feature
+/// `to_string` produces PEM instead.
+#[cfg(feature = "sec1")]
+impl<C> fmt::LowerHex for PublicKey<C>
+where
+    C: CurveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:x}", HexDisplay(&self.to_bytes()))
+    }
+}
+
+/// Writes the compressed SEC1 encoding in uppercase hex.
+#[cfg(feature = "sec1")]
+impl<C> fmt::UpperHex for PublicKey<C>
+where
+    C: CurveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:X}", HexDisplay(&self.to_bytes()))
+    }
+}
+
 #[cfg(feature = "sec1")]
 impl<C> TryFrom<CompressedPoint<C>> for PublicKey<C>
 where
@@ -456,17 +1271,92 @@ where
     }
 }
 
+#[cfg(feature = "pkcs8")]
+impl<C> PublicKey<C>
+where
+    C: AssociatedOid + CurveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    /// Decode a [`PublicKey`] from `bytes` in whichever encoding they appear
+    /// to be in, judging by how they start:
+    ///
+    /// - `0x30`, a DER `SEQUENCE`: an X.509 `SubjectPublicKeyInfo`, as
+    ///   [`DecodePublicKey::from_public_key_der`] decodes.
+    /// - `0x02`, `0x03` or `0x04`: a SEC1 point, as
+    ///   [`PublicKey::from_sec1_bytes`] decodes.
+    /// - `-----BEGIN`: a PEM `PUBLIC KEY` document, as
+    ///   `DecodePublicKey::from_public_key_pem` decodes. This requires the
+    ///   `pem` feature.
+    ///
+    /// The prefixes are disjoint, so exactly one decoder is tried and its
+    /// error is returned if it fails; no other decoder is tried as a
+    /// fallback. Anything else, including the SEC1 identity, PEM with leading
+    /// whitespace, and PEM without the `pem` feature, is an error.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        match bytes.first() {
+            Some(0x30) => Self::from_public_key_der(bytes).map_err(|_| Error),
+            Some(0x02..=0x04) => Self::from_sec1_bytes(bytes),
+            #[cfg(feature = "pem")]
+            Some(b'-') if bytes.starts_with(b"-----BEGIN") => {
+                let pem = core::str::from_utf8(bytes).map_err(|_| Error)?;
+                Self::from_public_key_pem(pem).map_err(|_| Error)
+            }
+            _ => Err(Error),
+        }
+    }
+}
+
 #[cfg(all(feature = "alloc", feature = "pkcs8"))]
-impl<C> EncodePublicKey for PublicKey<C>
+impl<C> PublicKey<C>
 where
     C: AssociatedOid + CurveArithmetic,
     AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
     FieldBytesSize<C>: ModulusSize,
 {
-    fn to_public_key_der(&self) -> pkcs8::spki::Result<der::Document> {
+    /// Build the owned X.509 `SubjectPublicKeyInfo` structure for this key,
+    /// for embedding in a PKCS#10 `CertificationRequestInfo` or other
+    /// larger ASN.1 structures.
+    ///
+    /// This contains the same fields as [`EncodePublicKey::to_public_key_der`],
+    /// which instead produces a standalone DER document.
+    pub fn to_csr_spki(&self) -> pkcs8::spki::Result<pkcs8::spki::SubjectPublicKeyInfoOwned> {
+        use der::referenced::RefToOwned;
+
+        let public_key_bytes = self.to_encoded_point(false);
+
+        Ok(pkcs8::SubjectPublicKeyInfoRef {
+            algorithm: pkcs8::AlgorithmIdentifierRef {
+                oid: ALGORITHM_OID,
+                parameters: Some((&C::OID).into()),
+            },
+            subject_public_key: der::asn1::BitStringRef::new(0, public_key_bytes.as_bytes())?,
+        }
+        .ref_to_owned())
+    }
+
+    /// Serialize this key as a DER-encoded X.509 `SubjectPublicKeyInfo`
+    /// like [`EncodePublicKey::to_public_key_der`], but with `algorithm_oid`
+    /// in place of [`ALGORITHM_OID`] and `curve_oid` in place of the curve's
+    /// own OID as the algorithm parameters.
+    ///
+    /// This is intended for protocols which identify keys with their own
+    /// OIDs. Nothing checks that the OIDs are meaningful for this key:
+    /// interoperability with whatever consumes the document is the caller's
+    /// responsibility.
+    ///
+    /// The output is strict DER: every length uses the shortest definite
+    /// form, and the optional elements are limited to the curve OID, so it
+    /// suits parsers which accept nothing else. For P-256 with the standard
+    /// OIDs that is always 91 bytes, with short-form lengths throughout.
+    pub fn to_public_key_der_with_oid(
+        &self,
+        algorithm_oid: pkcs8::ObjectIdentifier,
+        curve_oid: pkcs8::ObjectIdentifier,
+    ) -> pkcs8::spki::Result<der::Document> {
         let algorithm = pkcs8::AlgorithmIdentifierRef {
-            oid: ALGORITHM_OID,
-            parameters: Some((&C::OID).into()),
+            oid: algorithm_oid,
+            parameters: Some((&curve_oid).into()),
         };
 
         let public_key_bytes = self.to_encoded_point(false);
@@ -478,6 +1368,51 @@ where
         }
         .try_into()
     }
+
+    /// Serialize this key as a length-prefixed DER `SubjectPublicKeyInfo`,
+    /// for embedding in binary message formats which need self-describing
+    /// field lengths.
+    ///
+    /// The framing is the length of the DER document as a 2-byte big-endian
+    /// integer, followed by the document produced by
+    /// [`EncodePublicKey::to_public_key_der`].
+    pub fn to_length_prefixed_der(&self) -> Result<Vec<u8>> {
+        let der = self.to_public_key_der().map_err(|_| Error)?;
+        let len = u16::try_from(der.as_bytes().len()).map_err(|_| Error)?;
+
+        let mut out = Vec::with_capacity(2 + der.as_bytes().len());
+        out.extend_from_slice(&len.to_be_bytes());
+        out.extend_from_slice(der.as_bytes());
+        Ok(out)
+    }
+
+    /// Parse a key serialized by [`PublicKey::to_length_prefixed_der`].
+    ///
+    /// The length prefix must cover the rest of `bytes` exactly.
+    pub fn from_length_prefixed_der(bytes: &[u8]) -> Result<Self> {
+        let (len, der) = match bytes {
+            [hi, lo, der @ ..] => (usize::from(u16::from_be_bytes([*hi, *lo])), der),
+            _ => return Err(Error),
+        };
+
+        if der.len() != len {
+            return Err(Error);
+        }
+
+        Self::from_public_key_der(der).map_err(|_| Error)
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "pkcs8"))]
+impl<C> EncodePublicKey for PublicKey<C>
+where
+    C: AssociatedOid + CurveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    fn to_public_key_der(&self) -> pkcs8::spki::Result<der::Document> {
+        self.to_public_key_der_with_oid(ALGORITHM_OID, C::OID)
+    }
 }
 
 #[cfg(feature = "pem")]
@@ -523,6 +1458,8 @@ where
     }
 }
 
+/// Decodes SPKI DER with [`DecodePublicKey::from_public_key_der`], which
+/// rejects any data following the `SubjectPublicKeyInfo`.
 #[cfg(feature = "serde")]
 impl<'de, C> Deserialize<'de> for PublicKey<C>
 where
@@ -553,4 +1490,71 @@ mod tests {
             PublicKey::from_encoded_point(&identity).is_none()
         ));
     }
+
+    #[test]
+    fn verify_on_curve() {
+        use hex_literal::hex;
+
+        let public_key = PublicKey::from_sec1_bytes(&hex!("041CACFFB55F2F2CEFD89D89EB374B2681152452802DEEA09916068137D839CF7FC481A44492304D7EF66AC117BEFE83A8D08F155F2B52F9F618DD447029048E0F")).unwrap();
+        assert!(public_key.verify_on_curve());
+    }
+
+    #[test]
+    fn verify_on_curve_rejects_corrupted_points() {
+        use crate::dev::AffinePoint;
+
+        // A zeroed key wraps the identity, which no constructor accepts.
+        let zeroed = PublicKey {
+            point: AffinePoint::default(),
+        };
+        assert!(!zeroed.verify_on_curve());
+
+        // The identity's encoding wrapped as an ordinary point does not
+        // decode back to itself.
+        let disguised = PublicKey {
+            point: AffinePoint::Other(EncodedPoint::identity()),
+        };
+        assert!(!disguised.verify_on_curve());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_rejects_trailing_data() {
+        use alloc::format;
+        use hex_literal::hex;
+
+        let public_key = PublicKey::from_sec1_bytes(&hex!("041CACFFB55F2F2CEFD89D89EB374B2681152452802DEEA09916068137D839CF7FC481A44492304D7EF66AC117BEFE83A8D08F155F2B52F9F618DD447029048E0F")).unwrap();
+        let json = serde_json::to_string(&public_key).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PublicKey>(&json).unwrap(),
+            public_key
+        );
+
+        // Append a 0x00 byte to the hex-encoded SPKI DER.
+        let trailing = format!("{}00\"", &json[..json.len() - 1]);
+        assert!(serde_json::from_str::<PublicKey>(&trailing).is_err());
+    }
+
+    #[cfg(feature = "jwk")]
+    #[test]
+    fn matches_jwk() {
+        use crate::JwkEcKey;
+        use alloc::format;
+
+        let jwk = |crv: &str, y: &str| -> JwkEcKey {
+            format!(
+                r#"{{"kty":"EC","crv":"{crv}","x":"gI0GAILBdu7T53akrFmMyGcsF3n5dO7MmwNBHKW5SV0","y":"{y}"}}"#
+            )
+            .parse()
+            .unwrap()
+        };
+        let y = "SLW_xSffzlPWrHEVI30DHM_4egVwt3NQqeUD7nMFpps";
+
+        let public_key = PublicKey::from_jwk(&jwk("P-256", y)).unwrap();
+        assert!(public_key.matches_jwk(&jwk("P-256", y)));
+        assert!(!public_key.matches_jwk(&jwk("P-384", y)));
+        assert!(
+            !public_key.matches_jwk(&jwk("P-256", "TLW_xSffzlPWrHEVI30DHM_4egVwt3NQqeUD7nMFpps"))
+        );
+    }
 }
@@ -0,0 +1,57 @@
+//! Sums of scalar multiples of points, finalized into a [`PublicKey`].
+
+use super::PublicKey;
+use crate::{CurveArithmetic, ProjectivePoint, Result, Scalar};
+use group::{Curve as _, Group};
+
+/// Accumulates terms `scalar×point` and finalizes their sum into a
+/// [`PublicKey`], e.g. to evaluate a verification equation or a commitment
+/// with several generators.
+///
+/// Each term is currently computed with a separate scalar multiplication;
+/// the API leaves room for a multi-scalar multiplication later.
+#[derive(Clone, Copy, Debug)]
+pub struct PublicKeyAccumulator<C: CurveArithmetic> {
+    sum: ProjectivePoint<C>,
+}
+
+impl<C> PublicKeyAccumulator<C>
+where
+    C: CurveArithmetic,
+{
+    /// Create an accumulator holding the empty sum, i.e. the identity.
+    pub fn new() -> Self {
+        Self {
+            sum: ProjectivePoint::<C>::identity(),
+        }
+    }
+
+    /// Add `scalar×point` to the sum.
+    pub fn add_term(&mut self, scalar: &Scalar<C>, point: &PublicKey<C>) -> &mut Self {
+        self.sum += point.to_projective() * scalar;
+        self
+    }
+
+    /// Add `scalar×G` to the sum, where `G` is the curve's generator.
+    pub fn add_generator_term(&mut self, scalar: &Scalar<C>) -> &mut Self {
+        self.sum += ProjectivePoint::<C>::generator() * scalar;
+        self
+    }
+
+    /// Convert the sum into a [`PublicKey`].
+    ///
+    /// Returns an error if the sum is the identity, which includes the case
+    /// where no terms were added.
+    pub fn finalize(&self) -> Result<PublicKey<C>> {
+        PublicKey::from_affine(self.sum.to_affine())
+    }
+}
+
+impl<C> Default for PublicKeyAccumulator<C>
+where
+    C: CurveArithmetic,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,134 @@
+//! Fixed-layout public key representation for FFI.
+
+use super::PublicKey;
+use crate::{
+    sec1::{EncodedPoint, FromEncodedPoint, ModulusSize, Tag, ToEncodedPoint},
+    AffinePoint, CurveArithmetic, Error, FieldBytesSize, Result,
+};
+use generic_array::typenum::Unsigned;
+
+/// Large enough for a field element of the largest supported curve (P-521).
+const MAX_FIELD_SIZE: usize = 66;
+
+/// Uncompressed public key with a fixed, C-compatible memory layout.
+///
+/// ```c
+/// struct CPublicKey {
+///     uint8_t tag;      /* SEC1 tag: always 0x04 (uncompressed) */
+///     uint8_t x[66];    /* big-endian affine x-coordinate */
+///     uint8_t y[66];    /* big-endian affine y-coordinate */
+///     uint8_t len;      /* size of a field element for this curve */
+/// };
+/// ```
+///
+/// Only the first `len` bytes of `x` and `y` are meaningful, e.g. 32 for
+/// P-256, 48 for P-384, and 66 for P-521. The remaining bytes are zero.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(C)]
+pub struct CPublicKey {
+    /// SEC1 tag byte. Always [`Tag::Uncompressed`] (`0x04`).
+    pub tag: u8,
+
+    /// Affine x-coordinate, big-endian, in the first `len` bytes.
+    pub x: [u8; MAX_FIELD_SIZE],
+
+    /// Affine y-coordinate, big-endian, in the first `len` bytes.
+    pub y: [u8; MAX_FIELD_SIZE],
+
+    /// Number of valid bytes in `x` and `y`.
+    pub len: u8,
+}
+
+impl CPublicKey {
+    /// Size of the `x` and `y` coordinate buffers.
+    pub const MAX_FIELD_SIZE: usize = MAX_FIELD_SIZE;
+}
+
+impl<C> PublicKey<C>
+where
+    C: CurveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    /// Decode a [`PublicKey`] from a [`CPublicKey`].
+    ///
+    /// Returns an error if the tag isn't `0x04`, if `len` doesn't match the
+    /// field size of `C`, or if the coordinates aren't a valid point.
+    pub fn from_c_struct(c_key: &CPublicKey) -> Result<Self> {
+        let len = usize::from(c_key.len);
+
+        if c_key.tag != u8::from(Tag::Uncompressed) || len != C::FieldBytesSize::USIZE {
+            return Err(Error);
+        }
+
+        let point = EncodedPoint::<C>::from_affine_coordinates(
+            c_key.x[..len].into(),
+            c_key.y[..len].into(),
+            false,
+        );
+
+        Option::from(Self::from_encoded_point(&point)).ok_or(Error)
+    }
+
+    /// Encode this [`PublicKey`] as a [`CPublicKey`].
+    ///
+    /// # Panics
+    ///
+    /// If the field size of `C` exceeds [`CPublicKey::MAX_FIELD_SIZE`], which
+    /// is not the case for any supported curve.
+    pub fn to_c_struct(&self) -> CPublicKey {
+        let point = self.to_encoded_point(false);
+        let len = C::FieldBytesSize::USIZE;
+        assert!(len <= MAX_FIELD_SIZE, "field size exceeds MAX_FIELD_SIZE");
+
+        let mut c_key = CPublicKey {
+            tag: Tag::Uncompressed.into(),
+            x: [0; MAX_FIELD_SIZE],
+            y: [0; MAX_FIELD_SIZE],
+            len: len as u8,
+        };
+
+        if let (Some(x), Some(y)) = (point.x(), point.y()) {
+            c_key.x[..len].copy_from_slice(x);
+            c_key.y[..len].copy_from_slice(y);
+        }
+
+        c_key
+    }
+}
+
+#[cfg(all(feature = "dev", test))]
+mod tests {
+    use super::CPublicKey;
+    use crate::dev::MockCurve;
+    use hex_literal::hex;
+
+    type PublicKey = super::PublicKey<MockCurve>;
+
+    const EXAMPLE_PUBKEY: [u8; 65] = hex!("041CACFFB55F2F2CEFD89D89EB374B2681152452802DEEA09916068137D839CF7FC481A44492304D7EF66AC117BEFE83A8D08F155F2B52F9F618DD447029048E0F");
+
+    #[test]
+    fn c_struct_round_trip() {
+        let public_key = PublicKey::from_sec1_bytes(&EXAMPLE_PUBKEY).unwrap();
+        let c_key = public_key.to_c_struct();
+        assert_eq!(c_key.tag, 0x04);
+        assert_eq!(c_key.len, 32);
+        assert_eq!(&c_key.x[..32], &EXAMPLE_PUBKEY[1..33]);
+        assert_eq!(&c_key.y[..32], &EXAMPLE_PUBKEY[33..]);
+        assert!(c_key.x[32..].iter().chain(&c_key.y[32..]).all(|&b| b == 0));
+        assert_eq!(PublicKey::from_c_struct(&c_key).unwrap(), public_key);
+    }
+
+    #[test]
+    fn from_c_struct_rejects_bad_header() {
+        let c_key = PublicKey::from_sec1_bytes(&EXAMPLE_PUBKEY)
+            .unwrap()
+            .to_c_struct();
+
+        let bad_tag = CPublicKey { tag: 0x02, ..c_key };
+        assert!(PublicKey::from_c_struct(&bad_tag).is_err());
+
+        let bad_len = CPublicKey { len: 48, ..c_key };
+        assert!(PublicKey::from_c_struct(&bad_len).is_err());
+    }
+}
@@ -0,0 +1,100 @@
+//! OpenSSH public keys, as found in `authorized_keys` and `*.pub` files.
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc5656#section-3.1>
+
+use super::PublicKey;
+use crate::{
+    sec1::{EncodedPoint, FromEncodedPoint, ModulusSize, Tag, ToEncodedPoint},
+    AffinePoint, CurveArithmetic, Error, FieldBytesSize, Result,
+};
+use alloc::{format, string::String, vec::Vec};
+use base64ct::{Base64, Encoding};
+
+/// Prefix of the key type of every ECDSA OpenSSH key, which the curve's
+/// [`OpenSshParameters::SSH_CURVE`] completes.
+const KEY_TYPE_PREFIX: &str = "ecdsa-sha2-";
+
+/// Elliptic curve parameters used by OpenSSH public keys.
+pub trait OpenSshParameters: CurveArithmetic {
+    /// The curve identifier defined in RFC 5656 Section 10.1, e.g.
+    /// `"nistp256"`, `"nistp384"` or `"nistp521"`.
+    const SSH_CURVE: &'static str;
+}
+
+impl<C> PublicKey<C>
+where
+    C: OpenSshParameters,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    /// Serialize this [`PublicKey`] as an OpenSSH public key line without a
+    /// comment, e.g. `ecdsa-sha2-nistp256 AAAAE2VjZHNh...`.
+    pub fn to_openssh(&self) -> Result<String> {
+        let key_type = format!("{KEY_TYPE_PREFIX}{}", C::SSH_CURVE);
+        let point = self.to_encoded_point(false);
+
+        let mut blob = Vec::new();
+        put_string(&mut blob, key_type.as_bytes());
+        put_string(&mut blob, C::SSH_CURVE.as_bytes());
+        put_string(&mut blob, point.as_bytes());
+
+        Ok(format!("{key_type} {}", Base64::encode_string(&blob)))
+    }
+
+    /// Parse an OpenSSH public key line, e.g.
+    /// `ecdsa-sha2-nistp256 AAAAE2VjZHNh... user@host`; the comment is
+    /// optional and ignored.
+    ///
+    /// Returns an error if the key is not an ECDSA key on the curve `C`,
+    /// either in the line's key type or in the encoded blob, or if its point
+    /// is not a valid uncompressed point on the curve.
+    pub fn from_openssh(s: &str) -> Result<Self> {
+        let key_type = format!("{KEY_TYPE_PREFIX}{}", C::SSH_CURVE);
+        let mut fields = s.split_whitespace();
+        if fields.next() != Some(key_type.as_str()) {
+            return Err(Error);
+        }
+
+        let blob = Base64::decode_vec(fields.next().ok_or(Error)?).map_err(|_| Error)?;
+        let mut rest = blob.as_slice();
+        if take_string(&mut rest)? != key_type.as_bytes()
+            || take_string(&mut rest)? != C::SSH_CURVE.as_bytes()
+        {
+            return Err(Error);
+        }
+
+        let point = EncodedPoint::<C>::from_bytes(take_string(&mut rest)?)?;
+        if !rest.is_empty() || point.tag() != Tag::Uncompressed {
+            return Err(Error);
+        }
+
+        Option::from(Self::from_encoded_point(&point)).ok_or(Error)
+    }
+}
+
+/// Append `bytes` as an SSH `string`: a big-endian `u32` length, then the
+/// bytes themselves.
+fn put_string(blob: &mut Vec<u8>, bytes: &[u8]) {
+    // Every string written here is at most a key type or a SEC1 point.
+    let len = u32::try_from(bytes.len()).expect("SSH string too long");
+    blob.extend_from_slice(&len.to_be_bytes());
+    blob.extend_from_slice(bytes);
+}
+
+/// Split an SSH `string` off the front of `blob`.
+fn take_string<'a>(blob: &mut &'a [u8]) -> Result<&'a [u8]> {
+    if blob.len() < 4 {
+        return Err(Error);
+    }
+
+    let (len, rest) = blob.split_at(4);
+    let len = u32::from_be_bytes([len[0], len[1], len[2], len[3]]);
+    let len = usize::try_from(len).map_err(|_| Error)?;
+    if rest.len() < len {
+        return Err(Error);
+    }
+
+    let (string, rest) = rest.split_at(len);
+    *blob = rest;
+    Ok(string)
+}
@@ -0,0 +1,107 @@
+//! `serde` support for [`PublicKey`] as PEM in human-readable formats.
+//!
+//! The [`Serialize`][serdect::serde::Serialize] impl on [`PublicKey`] encodes
+//! SPKI DER as hex in human-readable formats, which is hard to check or edit
+//! by hand. This module uses a PEM string instead, so keys in YAML or TOML
+//! config files look like the output of `openssl pkey -pubout`. Binary
+//! formats still get the DER bytes.
+//!
+//! Opt in per field with `#[serde(with = ...)]`:
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde(with = "elliptic_curve::pem_serde")]
+//!     server_key: p256::PublicKey,
+//! }
+//! ```
+
+use super::PublicKey;
+use crate::{
+    pkcs8::{AssociatedOid, DecodePublicKey, EncodePublicKey, LineEnding},
+    sec1::{FromEncodedPoint, ModulusSize, ToEncodedPoint},
+    AffinePoint, CurveArithmetic, FieldBytesSize,
+};
+use alloc::string::String;
+use serdect::serde::{de, ser, Deserialize};
+
+/// Serialize `public_key` as SPKI PEM with LF line endings, or as SPKI DER
+/// bytes if the format is not human-readable.
+pub fn serialize<C, S>(public_key: &PublicKey<C>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    C: AssociatedOid + CurveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+    S: ser::Serializer,
+{
+    if serializer.is_human_readable() {
+        let pem = public_key
+            .to_public_key_pem(LineEnding::LF)
+            .map_err(ser::Error::custom)?;
+        serializer.serialize_str(&pem)
+    } else {
+        let der = public_key.to_public_key_der().map_err(ser::Error::custom)?;
+        serdect::slice::serialize_hex_upper_or_bin(&der, serializer)
+    }
+}
+
+/// Deserialize a [`PublicKey`] from SPKI PEM, or from SPKI DER bytes if the
+/// format is not human-readable.
+pub fn deserialize<'de, C, D>(deserializer: D) -> Result<PublicKey<C>, D::Error>
+where
+    C: AssociatedOid + CurveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+    D: de::Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        let pem = String::deserialize(deserializer)?;
+        PublicKey::from_public_key_pem(&pem).map_err(de::Error::custom)
+    } else {
+        let der = serdect::slice::deserialize_hex_or_bin_vec(deserializer)?;
+        PublicKey::from_public_key_der(&der).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(all(feature = "dev", test))]
+mod tests {
+    use crate::dev::MockCurve;
+    use hex_literal::hex;
+    use serdect::serde::{de, ser, Deserialize, Serialize};
+
+    type PublicKey = super::PublicKey<MockCurve>;
+
+    const EXAMPLE_PUBKEY: [u8; 65] = hex!("041CACFFB55F2F2CEFD89D89EB374B2681152452802DEEA09916068137D839CF7FC481A44492304D7EF66AC117BEFE83A8D08F155F2B52F9F618DD447029048E0F");
+
+    /// What `#[serde(with = "pem_serde")]` expands to.
+    #[derive(Debug, PartialEq)]
+    struct Pem(PublicKey);
+
+    impl Serialize for Pem {
+        fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            super::serialize(&self.0, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Pem {
+        fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            super::deserialize(deserializer).map(Self)
+        }
+    }
+
+    #[test]
+    fn yaml_round_trip() {
+        let key = Pem(PublicKey::from_sec1_bytes(&EXAMPLE_PUBKEY).unwrap());
+        let yaml = serde_yaml::to_string(&key).unwrap();
+        assert!(yaml.contains("-----BEGIN PUBLIC KEY-----"));
+        assert_eq!(serde_yaml::from_str::<Pem>(&yaml).unwrap(), key);
+    }
+
+    #[test]
+    fn bincode_round_trip() {
+        let key = Pem(PublicKey::from_sec1_bytes(&EXAMPLE_PUBKEY).unwrap());
+        let bytes = bincode::serialize(&key).unwrap();
+        assert_eq!(bytes.len(), 8 + 91);
+        assert_eq!(bincode::deserialize::<Pem>(&bytes).unwrap(), key);
+    }
+}
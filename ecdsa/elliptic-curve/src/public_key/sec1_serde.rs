@@ -0,0 +1,98 @@
+//! `serde` support for [`PublicKey`] as a compressed SEC1 point.
+//!
+//! The [`Serialize`][serdect::serde::Serialize] impl on [`PublicKey`] uses
+//! SPKI DER, which for P-256 is 91 bytes. This module serializes the 33-byte
+//! compressed SEC1 point instead, as upper-case hex in human-readable formats
+//! and raw bytes otherwise, for space-constrained formats such as CBOR.
+//!
+//! Opt in per field with `#[serde(with = ...)]`:
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Message {
+//!     #[serde(with = "elliptic_curve::sec1_serde")]
+//!     sender: p256::PublicKey,
+//! }
+//! ```
+//!
+//! Deserialization accepts any SEC1 encoding [`PublicKey::from_sec1_bytes`]
+//! does, including uncompressed points.
+
+use super::PublicKey;
+use crate::{
+    sec1::{FromEncodedPoint, ModulusSize, ToEncodedPoint},
+    AffinePoint, CurveArithmetic, FieldBytesSize,
+};
+use serdect::serde::{de, ser};
+
+/// Serialize `public_key` as a compressed SEC1 point.
+pub fn serialize<C, S>(public_key: &PublicKey<C>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    C: CurveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+    S: ser::Serializer,
+{
+    serdect::slice::serialize_hex_upper_or_bin(&public_key.to_bytes(), serializer)
+}
+
+/// Deserialize a [`PublicKey`] from a SEC1 point.
+pub fn deserialize<'de, C, D>(deserializer: D) -> Result<PublicKey<C>, D::Error>
+where
+    C: CurveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+    D: de::Deserializer<'de>,
+{
+    let bytes = serdect::slice::deserialize_hex_or_bin_vec(deserializer)?;
+    PublicKey::from_sec1_bytes(&bytes).map_err(de::Error::custom)
+}
+
+#[cfg(all(feature = "dev", test))]
+mod tests {
+    use crate::dev::MockCurve;
+    use alloc::format;
+    use hex_literal::hex;
+    use serdect::serde::{de, ser, Deserialize, Serialize};
+
+    type PublicKey = super::PublicKey<MockCurve>;
+
+    /// MockCurve can't compress points, so the keys start out compressed.
+    const COMPRESSED_PUBKEY: [u8; 33] =
+        hex!("031CACFFB55F2F2CEFD89D89EB374B2681152452802DEEA09916068137D839CF7F");
+
+    const COMPRESSED_HEX: &str =
+        "031CACFFB55F2F2CEFD89D89EB374B2681152452802DEEA09916068137D839CF7F";
+
+    /// What `#[serde(with = "sec1_serde")]` expands to.
+    #[derive(Debug, PartialEq)]
+    struct Compressed(PublicKey);
+
+    impl Serialize for Compressed {
+        fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            super::serialize(&self.0, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Compressed {
+        fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            super::deserialize(deserializer).map(Self)
+        }
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let key = Compressed(PublicKey::from_sec1_bytes(&COMPRESSED_PUBKEY).unwrap());
+        let json = serde_json::to_string(&key).unwrap();
+        assert_eq!(json, format!("\"{COMPRESSED_HEX}\""));
+        assert_eq!(serde_json::from_str::<Compressed>(&json).unwrap(), key);
+    }
+
+    #[test]
+    fn bincode_round_trip() {
+        let key = Compressed(PublicKey::from_sec1_bytes(&COMPRESSED_PUBKEY).unwrap());
+        let bytes = bincode::serialize(&key).unwrap();
+        assert_eq!(bytes.len(), 8 + 33);
+        assert_eq!(bincode::deserialize::<Compressed>(&bytes).unwrap(), key);
+    }
+}
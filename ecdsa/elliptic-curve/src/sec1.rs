@@ -26,6 +26,19 @@ pub type UncompressedPoint<C> = GenericArray<u8, UncompressedPointSize<C>>;
 /// Size of an uncompressed elliptic curve point.
 pub type UncompressedPointSize<C> = <FieldBytesSize<C> as ModulusSize>::UncompressedPointSize;
 
+/// Whether the SEC1 encoding `bytes` is of a compressed point, judging by its
+/// leading tag byte alone.
+///
+/// Returns `Some(true)` for `0x02`/`0x03` and `Some(false)` for the other
+/// tags SEC1 defines: `0x04` (uncompressed), `0x05` (compact) and `0x00`
+/// (identity), matching [`Tag::is_compressed`]. Returns `None` if `bytes` is
+/// empty or its tag is unrecognized. The rest of `bytes`, including its
+/// length, is not checked.
+pub fn sec1_is_compressed(bytes: &[u8]) -> Option<bool> {
+    let tag = Tag::from_u8(*bytes.first()?).ok()?;
+    Some(tag.is_compressed())
+}
+
 /// Trait for deserializing a value from a SEC1 encoded curve point.
 ///
 /// This is intended for use with the `AffinePoint` type for a given elliptic curve.
@@ -112,3 +125,25 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::sec1_is_compressed;
+
+    #[test]
+    fn sec1_is_compressed_tags() {
+        assert_eq!(sec1_is_compressed(&[0x00]), Some(false));
+        assert_eq!(sec1_is_compressed(&[0x02, 0xAA]), Some(true));
+        assert_eq!(sec1_is_compressed(&[0x03, 0xAA]), Some(true));
+        assert_eq!(sec1_is_compressed(&[0x04, 0xAA, 0xBB]), Some(false));
+        assert_eq!(sec1_is_compressed(&[0x05, 0xAA]), Some(false));
+    }
+
+    #[test]
+    fn sec1_is_compressed_unrecognized() {
+        assert_eq!(sec1_is_compressed(&[]), None);
+        assert_eq!(sec1_is_compressed(&[0x01]), None);
+        assert_eq!(sec1_is_compressed(&[0x06, 0xAA]), None);
+        assert_eq!(sec1_is_compressed(&[0xFF]), None);
+    }
+}
@@ -36,6 +36,21 @@ fn decode_pkcs8_private_key_from_der() {
     assert_eq!(secret_key.to_bytes().as_slice(), &EXAMPLE_SCALAR);
 }
 
+#[test]
+fn decode_pkcs8_public_key_rejects_trailing_data() {
+    let mut der_bytes = PKCS8_PUBLIC_KEY_DER.to_vec();
+    der_bytes.push(0);
+
+    let err = PublicKey::from_public_key_der(&der_bytes).unwrap_err();
+    assert!(matches!(
+        err,
+        pkcs8::spki::Error::Asn1(err) if matches!(
+            err.kind(),
+            der::ErrorKind::TrailingData { decoded, remaining } if decoded == 91u8.into() && remaining == 1u8.into()
+        )
+    ));
+}
+
 #[test]
 fn decode_pkcs8_public_key_from_der() {
     let public_key = PublicKey::from_public_key_der(&PKCS8_PUBLIC_KEY_DER[..]).unwrap();
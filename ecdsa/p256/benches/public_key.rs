@@ -0,0 +1,75 @@
+//! secp256r1 public key serialization benchmarks
+
+use criterion::{
+    criterion_group, criterion_main, measurement::Measurement, BenchmarkGroup, Criterion,
+    Throughput,
+};
+use hex_literal::hex;
+use p256::{
+    elliptic_curve::{
+        pkcs8::{DecodePublicKey, EncodePublicKey},
+        sec1::ToEncodedPoint,
+    },
+    PublicKey, SecretKey,
+};
+
+fn test_public_key() -> PublicKey {
+    SecretKey::from_slice(&hex!(
+        "519b423d715f8b581f4fa8ee59f4771a5b44c8130b4e3eacca54a56dda72b464"
+    ))
+    .unwrap()
+    .public_key()
+}
+
+/// Benchmark one encoding, reporting its throughput in bytes of output so
+/// both the per-key cost and the per-key size show up in the results.
+fn bench_encoding<'a, M, T, F>(group: &mut BenchmarkGroup<'a, M>, name: &str, encode: F)
+where
+    M: Measurement,
+    T: AsRef<[u8]>,
+    F: Fn(&PublicKey) -> T,
+{
+    let pk = test_public_key();
+    let len = encode(&pk).as_ref().len();
+    group.throughput(Throughput::Bytes(len as u64));
+    group.bench_function(format!("{name} ({len} bytes)"), |b| b.iter(|| encode(&pk)));
+}
+
+fn bench_serialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("public key serialization");
+    bench_encoding(&mut group, "DER SPKI", |pk| {
+        pk.to_public_key_der().unwrap().into_vec()
+    });
+    bench_encoding(&mut group, "SEC1 compressed", |pk| {
+        pk.to_encoded_point(true)
+    });
+    bench_encoding(&mut group, "SEC1 uncompressed", |pk| {
+        pk.to_encoded_point(false)
+    });
+    bench_encoding(&mut group, "JWK", |pk| pk.to_jwk_string());
+    group.finish();
+}
+
+fn bench_deserialize(c: &mut Criterion) {
+    let pk = test_public_key();
+    let der = pk.to_public_key_der().unwrap();
+    let compressed = pk.to_encoded_point(true);
+    let uncompressed = pk.to_encoded_point(false);
+    let jwk = pk.to_jwk_string();
+
+    let mut group = c.benchmark_group("public key deserialization");
+    group.bench_function("DER SPKI", |b| {
+        b.iter(|| PublicKey::from_public_key_der(der.as_bytes()))
+    });
+    group.bench_function("SEC1 compressed", |b| {
+        b.iter(|| PublicKey::from_sec1_bytes(compressed.as_bytes()))
+    });
+    group.bench_function("SEC1 uncompressed", |b| {
+        b.iter(|| PublicKey::from_sec1_bytes(uncompressed.as_bytes()))
+    });
+    group.bench_function("JWK", |b| b.iter(|| PublicKey::from_jwk_str(&jwk)));
+    group.finish();
+}
+
+criterion_group!(benches, bench_serialize, bench_deserialize);
+criterion_main!(benches);
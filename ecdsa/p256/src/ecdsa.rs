@@ -41,6 +41,12 @@
 
 pub use ecdsa_core::signature::{self, Error};
 
+#[cfg(feature = "ecdsa")]
+pub use ecdsa_core::PublicKeyExt;
+
+#[cfg(feature = "x509")]
+pub use ecdsa_core::CertificateError;
+
 use super::NistP256;
 
 #[cfg(feature = "ecdsa")]
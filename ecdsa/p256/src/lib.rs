@@ -110,6 +110,12 @@ impl elliptic_curve::Curve for NistP256 {
 
 impl elliptic_curve::PrimeCurve for NistP256 {}
 
+#[cfg(feature = "arithmetic")]
+impl elliptic_curve::FieldModulus for NistP256 {
+    /// Modulus of NIST P-256's base field.
+    const FIELD_MODULUS: U256 = arithmetic::field::MODULUS;
+}
+
 impl elliptic_curve::point::PointCompression for NistP256 {
     /// NIST P-256 points are typically uncompressed.
     const COMPRESS_POINTS: bool = false;
@@ -125,6 +131,11 @@ impl elliptic_curve::JwkParameters for NistP256 {
     const CRV: &'static str = "P-256";
 }
 
+#[cfg(feature = "openssh")]
+impl elliptic_curve::OpenSshParameters for NistP256 {
+    const SSH_CURVE: &'static str = "nistp256";
+}
+
 #[cfg(feature = "pkcs8")]
 impl pkcs8::AssociatedOid for NistP256 {
     const OID: pkcs8::ObjectIdentifier = pkcs8::ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7");
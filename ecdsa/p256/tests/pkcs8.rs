@@ -97,3 +97,53 @@ fn encode_pkcs8_public_key_to_pem() {
     let reencoded_public_key = original_public_key.to_string();
     assert_eq!(reencoded_public_key.as_str(), PKCS8_PUBLIC_KEY_PEM);
 }
+
+#[test]
+fn from_slice_der_and_sec1() {
+    let public_key = p256::PublicKey::from_public_key_der(&PKCS8_PUBLIC_KEY_DER[..]).unwrap();
+    assert_eq!(
+        p256::PublicKey::from_slice(&PKCS8_PUBLIC_KEY_DER[..]).unwrap(),
+        public_key
+    );
+
+    // The SPKI document ends with the uncompressed SEC1 point, which on its
+    // own is dispatched by its 0x04 tag.
+    let sec1 = &PKCS8_PUBLIC_KEY_DER[26..];
+    assert_eq!(sec1, public_key.to_encoded_point(false).as_bytes());
+    assert_eq!(p256::PublicKey::from_slice(sec1).unwrap(), public_key);
+    assert_eq!(
+        p256::PublicKey::from_slice(public_key.to_encoded_point(true).as_bytes()).unwrap(),
+        public_key
+    );
+}
+
+#[test]
+fn from_slice_rejects_unrecognized_and_malformed_inputs() {
+    assert!(p256::PublicKey::from_slice(&[]).is_err());
+    assert!(p256::PublicKey::from_slice(&[0x00]).is_err());
+    assert!(p256::PublicKey::from_slice(&PKCS8_PUBLIC_KEY_DER[..90]).is_err());
+
+    // A 0x30 prefix commits to DER: the SEC1 point after it isn't tried.
+    let mut sequence_then_point = vec![0x30];
+    sequence_then_point.extend_from_slice(&PKCS8_PUBLIC_KEY_DER[26..]);
+    assert!(p256::PublicKey::from_slice(&sequence_then_point).is_err());
+
+    // Likewise a SEC1 tag followed by an SPKI document.
+    let mut tag_then_der = vec![0x04];
+    tag_then_der.extend_from_slice(&PKCS8_PUBLIC_KEY_DER[..]);
+    assert!(p256::PublicKey::from_slice(&tag_then_der).is_err());
+}
+
+#[test]
+#[cfg(feature = "pem")]
+fn from_slice_pem() {
+    let public_key = p256::PublicKey::from_public_key_der(&PKCS8_PUBLIC_KEY_DER[..]).unwrap();
+    assert_eq!(
+        p256::PublicKey::from_slice(PKCS8_PUBLIC_KEY_PEM.as_bytes()).unwrap(),
+        public_key
+    );
+
+    let indented = format!("\n{PKCS8_PUBLIC_KEY_PEM}");
+    assert!(p256::PublicKey::from_slice(indented.as_bytes()).is_err());
+    assert!(p256::PublicKey::from_slice(PKCS8_PRIVATE_KEY_PEM.as_bytes()).is_err());
+}
@@ -0,0 +1,870 @@
+//! Public key tests.
+
+#![cfg(feature = "arithmetic")]
+
+use hex_literal::hex;
+use p256::{elliptic_curve::sec1::ToEncodedPoint, NonZeroScalar, PublicKey, Scalar};
+
+const COMPRESSED_BASEPOINT: &[u8] =
+    &hex!("03 6B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296");
+
+fn scalar(n: u64) -> NonZeroScalar {
+    NonZeroScalar::new(Scalar::from(n)).unwrap()
+}
+
+#[test]
+fn is_generator_multiple() {
+    let k = scalar(42);
+    let public_key = PublicKey::from_secret_scalar(&k);
+    assert!(public_key.is_generator_multiple(&k));
+    assert!(!public_key.is_generator_multiple(&scalar(43)));
+    assert!(!public_key.is_generator_multiple(&-k));
+}
+
+#[test]
+fn verify_on_curve() {
+    for n in [1, 42] {
+        assert!(PublicKey::from_secret_scalar(&scalar(n)).verify_on_curve());
+    }
+}
+
+#[test]
+fn diffie_hellman_shared_point() {
+    let (alice, bob) = (scalar(42), scalar(7));
+    let alice_public = PublicKey::from_secret_scalar(&alice);
+    let bob_public = PublicKey::from_secret_scalar(&bob);
+
+    let alice_shared = PublicKey::diffie_hellman(&alice, &bob_public).to_point();
+    let bob_shared = PublicKey::diffie_hellman(&bob, &alice_public).to_point();
+    assert_eq!(alice_shared, bob_shared);
+    assert_eq!(
+        alice_shared,
+        *PublicKey::from_secret_scalar(&scalar(42 * 7)).as_affine()
+    );
+}
+
+#[test]
+fn tagged_parts_round_trip() {
+    let public_key = PublicKey::from_secret_scalar(&scalar(1));
+    let (tag, x) = public_key.to_tagged_parts();
+    assert_eq!(tag, COMPRESSED_BASEPOINT[0]);
+    assert_eq!(x.as_slice(), &COMPRESSED_BASEPOINT[1..]);
+    assert_eq!(PublicKey::from_tagged_parts(tag, &x).unwrap(), public_key);
+}
+
+#[test]
+fn from_tagged_parts_rejects_non_compressed_tags() {
+    let (_, x) = PublicKey::from_secret_scalar(&scalar(1)).to_tagged_parts();
+
+    for tag in [0x00, 0x04, 0x05] {
+        assert!(PublicKey::from_tagged_parts(tag, &x).is_err());
+    }
+}
+
+#[test]
+fn from_coordinates_round_trip() {
+    use p256::FieldBytes;
+
+    let public_key = PublicKey::from_secret_scalar(&scalar(42));
+    let point = public_key.to_encoded_point(false);
+    let (x, y) = (point.x().unwrap(), point.y().unwrap());
+    assert_eq!(PublicKey::from_coordinates(x, y).unwrap(), public_key);
+
+    // Off the curve.
+    let mut y_plus_one = *y;
+    y_plus_one[31] ^= 1;
+    assert!(PublicKey::from_coordinates(x, &y_plus_one).is_err());
+
+    // A coordinate equal to the field modulus isn't canonical.
+    let p = FieldBytes::from(hex!(
+        "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"
+    ));
+    assert!(PublicKey::from_coordinates(&p, y).is_err());
+    assert!(PublicKey::from_coordinates(&FieldBytes::default(), &FieldBytes::default()).is_err());
+}
+
+#[test]
+fn coordinates() {
+    let generator = PublicKey::from_secret_scalar(&scalar(1));
+    assert_eq!(generator.x().as_slice(), &COMPRESSED_BASEPOINT[1..]);
+    assert_eq!(
+        generator.y(),
+        hex!("4FE342E2FE1A7F9B8EE7EB4A7C0F9E162BCE33576B315ECECBB6406837BF51F5").into()
+    );
+
+    let public_key = PublicKey::from_secret_scalar(&scalar(42));
+    let uncompressed = public_key.to_encoded_point(false);
+    assert_eq!(&public_key.x()[..], &uncompressed.as_bytes()[1..33]);
+    assert_eq!(&public_key.y()[..], &uncompressed.as_bytes()[33..]);
+    assert_eq!(
+        PublicKey::from_coordinates(&public_key.x(), &public_key.y()).unwrap(),
+        public_key
+    );
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn zeroize_leaves_a_valid_key() {
+    use p256::elliptic_curve::zeroize::Zeroize;
+
+    let mut public_key = PublicKey::from_secret_scalar(&scalar(42));
+    public_key.zeroize();
+
+    let generator = PublicKey::from_secret_scalar(&scalar(1));
+    assert_eq!(public_key, generator);
+    assert!(public_key.verify_on_curve());
+    assert_eq!(public_key.x().as_slice(), &COMPRESSED_BASEPOINT[1..]);
+    assert_eq!(public_key.y(), generator.y());
+    assert_eq!(
+        PublicKey::from_sec1_bytes(public_key.to_encoded_point(true).as_bytes()).unwrap(),
+        generator
+    );
+    assert_eq!(PublicKey::from(public_key.to_nonidentity()), generator);
+}
+
+#[test]
+fn hex_formatting() {
+    let generator = PublicKey::from_secret_scalar(&scalar(1));
+    assert_eq!(
+        format!("{generator:X}"),
+        "036B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296"
+    );
+
+    let public_key = PublicKey::from_secret_scalar(&scalar(42));
+    let hex = format!("{public_key:x}");
+    assert_eq!(hex, hex.to_lowercase());
+    let bytes: Vec<u8> = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect();
+    assert_eq!(PublicKey::from_sec1_bytes(&bytes).unwrap(), public_key);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn from_sec1_bytes_batch() {
+    let uncompressed = PublicKey::from_secret_scalar(&scalar(42)).to_encoded_point(false);
+    let items: [&[u8]; 3] = [
+        COMPRESSED_BASEPOINT,
+        &[0x02, 0xff, 0x00],
+        uncompressed.as_bytes(),
+    ];
+
+    let keys = PublicKey::from_sec1_bytes_batch(&items);
+    assert_eq!(keys.len(), 3);
+    assert_eq!(keys[0], Ok(PublicKey::from_secret_scalar(&scalar(1))));
+    assert!(keys[1].is_err());
+    assert_eq!(keys[2], Ok(PublicKey::from_secret_scalar(&scalar(42))));
+    assert!(PublicKey::from_sec1_bytes_batch(&[]).is_empty());
+}
+
+#[test]
+fn try_from_encoded_point() {
+    use p256::{elliptic_curve::DecodeError, EncodedPoint};
+
+    let basepoint = EncodedPoint::from_bytes(COMPRESSED_BASEPOINT).unwrap();
+    assert_eq!(
+        PublicKey::try_from_encoded_point(&basepoint),
+        Ok(PublicKey::from_secret_scalar(&scalar(1)))
+    );
+
+    let mut off_curve = [0; 65];
+    off_curve.copy_from_slice(
+        PublicKey::from_secret_scalar(&scalar(1))
+            .to_encoded_point(false)
+            .as_bytes(),
+    );
+    off_curve[64] ^= 1;
+    let off_curve = EncodedPoint::from_bytes(off_curve).unwrap();
+    assert_eq!(
+        PublicKey::try_from_encoded_point(&off_curve),
+        Err(DecodeError::NotOnCurve)
+    );
+
+    assert_eq!(
+        PublicKey::try_from_encoded_point(&EncodedPoint::identity()),
+        Err(DecodeError::Identity)
+    );
+
+    let mut unreduced = [0xff; 33];
+    unreduced[0] = 0x02;
+    let unreduced = EncodedPoint::from_bytes(unreduced).unwrap();
+    assert_eq!(
+        PublicKey::try_from_encoded_point(&unreduced),
+        Err(DecodeError::InvalidEncoding)
+    );
+}
+
+#[test]
+fn to_bytes_is_compressed() {
+    let bytes: [u8; 33] = PublicKey::from_secret_scalar(&scalar(1)).to_bytes().into();
+    assert_eq!(bytes, COMPRESSED_BASEPOINT);
+
+    let public_key = PublicKey::from_secret_scalar(&scalar(42));
+    assert_eq!(
+        public_key.to_bytes().as_slice(),
+        public_key.to_encoded_point(true).as_bytes()
+    );
+}
+
+#[test]
+fn eq_bytes_ignores_compression() {
+    let public_key = PublicKey::from_secret_scalar(&scalar(42));
+    let compressed = public_key.to_encoded_point(true);
+    let uncompressed = public_key.to_encoded_point(false);
+    assert!(PublicKey::eq_bytes(compressed.as_bytes(), uncompressed.as_bytes()).unwrap());
+    assert!(PublicKey::eq_bytes(uncompressed.as_bytes(), compressed.as_bytes()).unwrap());
+
+    let other = PublicKey::from_secret_scalar(&scalar(43)).to_encoded_point(false);
+    assert!(!PublicKey::eq_bytes(compressed.as_bytes(), other.as_bytes()).unwrap());
+
+    // The negation shares the x-coordinate but not the y-parity.
+    let negated = public_key.negate().to_encoded_point(true);
+    assert!(!PublicKey::eq_bytes(compressed.as_bytes(), negated.as_bytes()).unwrap());
+
+    assert!(PublicKey::eq_bytes(compressed.as_bytes(), &[0x00]).is_err());
+}
+
+#[test]
+fn compress_decompress_round_trip() {
+    let public_key = PublicKey::from_secret_scalar(&scalar(42));
+    let compressed = public_key.compress();
+    assert_eq!(compressed, public_key.to_bytes());
+
+    let decompressed = PublicKey::from_sec1_bytes(&compressed)
+        .unwrap()
+        .decompress();
+    assert!(!decompressed.is_compressed());
+    assert_eq!(decompressed, public_key.to_encoded_point(false));
+    assert_eq!(
+        PublicKey::from_sec1_bytes(decompressed.as_bytes()).unwrap(),
+        public_key
+    );
+}
+
+#[cfg(feature = "ecdsa")]
+#[test]
+fn verify_digest() {
+    use p256::ecdsa::{signature::Signer, PublicKeyExt, Signature, SigningKey};
+    use sha2::{Digest, Sha256};
+
+    let signing_key = SigningKey::from(scalar(42));
+    let public_key = PublicKey::from(signing_key.verifying_key());
+    let signature: Signature = signing_key.sign(b"hello world");
+
+    let mut digest = Sha256::new();
+    digest.update(b"hello ");
+    digest.update(b"world");
+    assert!(public_key.verify_digest(digest, &signature).is_ok());
+    assert!(public_key
+        .verify_digest(Sha256::new_with_prefix(b"goodbye"), &signature)
+        .is_err());
+}
+
+/// Points on the quadratic twist 𝑑𝑦² = 𝑥³ − 3𝑥 + 𝑏 of P-256 must be
+/// rejected by the SEC1 decoder.
+///
+/// Since 𝑝 ≡ 3 (mod 4), 𝑑 = −1 is a non-residue, so the twist points are
+/// those where −(𝑥³ − 3𝑥 + 𝑏) is a square while 𝑥³ − 3𝑥 + 𝑏 itself is not.
+#[cfg(feature = "expose-field")]
+#[test]
+fn reject_twist_points() {
+    use p256::{
+        elliptic_curve::{ff::PrimeField, sec1::FromEncodedPoint},
+        EncodedPoint, FieldBytes, FieldElement,
+    };
+
+    let b = FieldElement::from_repr(FieldBytes::clone_from_slice(&hex!(
+        "5AC635D8AA3A93E7B3EBBD55769886BC651D06B0CC53B0F63BCE3C3E27D2604B"
+    )))
+    .unwrap();
+    let rhs = |x: FieldElement| x.square() * x - x.double() - x + b;
+
+    let mut twist_points = 0;
+    for i in 1..64 {
+        let x = FieldElement::from_u64(i);
+        if rhs(x).sqrt().is_some().into() {
+            continue;
+        }
+
+        let y = (-rhs(x)).sqrt().unwrap();
+        assert_eq!(-y.square(), rhs(x), "({i}, y) is not on the twist");
+
+        let uncompressed = EncodedPoint::from_affine_coordinates(&x.to_repr(), &y.to_repr(), false);
+        assert!(PublicKey::from_sec1_bytes(uncompressed.as_bytes()).is_err());
+        assert!(PublicKey::reject_twist_points(&uncompressed).is_err());
+        assert!(bool::from(
+            PublicKey::from_encoded_point(&uncompressed).is_none()
+        ));
+
+        for tag in [0x02, 0x03] {
+            assert!(PublicKey::from_tagged_parts(tag, &x.to_repr()).is_err());
+        }
+
+        twist_points += 1;
+    }
+
+    // Roughly half of all x-coordinates belong to the twist.
+    assert!(twist_points > 16, "only found {twist_points} twist points");
+
+    let on_curve = PublicKey::from_secret_scalar(&scalar(7)).to_encoded_point(false);
+    assert!(PublicKey::reject_twist_points(&on_curve).is_ok());
+}
+
+#[cfg(feature = "expose-field")]
+#[test]
+fn from_projective_bytes() {
+    use p256::{elliptic_curve::ff::PrimeField, FieldElement};
+
+    let public_key = PublicKey::from_secret_scalar(&scalar(42));
+    let point = public_key.to_encoded_point(false);
+    let x = FieldElement::from_repr(*point.x().unwrap()).unwrap();
+    let y = FieldElement::from_repr(*point.y().unwrap()).unwrap();
+    let projective = |x: FieldElement, y: FieldElement, z: FieldElement| {
+        [x.to_repr(), y.to_repr(), z.to_repr()].concat()
+    };
+
+    // Any non-zero Z scales to the same affine point.
+    for z in [1, 2, 0xdead_beef] {
+        let z = FieldElement::from_u64(z);
+        let bytes = projective(x * z, y * z, z);
+        assert_eq!(
+            PublicKey::from_projective_bytes(&bytes).unwrap(),
+            public_key
+        );
+    }
+
+    let z = FieldElement::from_u64(3);
+    let off_curve = projective(x * z, (y + FieldElement::ONE) * z, z);
+    assert!(PublicKey::from_projective_bytes(&off_curve).is_err());
+
+    let identity = projective(FieldElement::ZERO, FieldElement::ONE, FieldElement::ZERO);
+    assert!(PublicKey::from_projective_bytes(&identity).is_err());
+
+    // X = 2²⁵⁶ - 1 is not reduced modulo p.
+    let mut not_canonical = projective(x, y, FieldElement::ONE);
+    not_canonical[..32].fill(0xff);
+    assert!(PublicKey::from_projective_bytes(&not_canonical).is_err());
+
+    let bytes = projective(x, y, FieldElement::ONE);
+    assert!(PublicKey::from_projective_bytes(&bytes[..64]).is_err());
+}
+
+/// Points on a curve with a different 𝑏 coefficient must be rejected too.
+#[test]
+fn reject_invalid_curve_points() {
+    use p256::EncodedPoint;
+
+    let point = PublicKey::from_secret_scalar(&scalar(1)).to_encoded_point(false);
+    let mut y = *point.y().unwrap();
+    y[31] ^= 1;
+
+    let invalid = EncodedPoint::from_affine_coordinates(point.x().unwrap(), &y, false);
+    assert!(PublicKey::from_sec1_bytes(invalid.as_bytes()).is_err());
+    assert!(PublicKey::reject_twist_points(&invalid).is_err());
+}
+
+#[test]
+fn point_eq_agrees_with_eq() {
+    use p256::elliptic_curve::subtle::ConstantTimeEq;
+
+    let compressed = PublicKey::from_sec1_bytes(COMPRESSED_BASEPOINT).unwrap();
+    let uncompressed =
+        PublicKey::from_sec1_bytes(compressed.to_encoded_point(false).as_bytes()).unwrap();
+    let other = PublicKey::from_secret_scalar(&scalar(2));
+
+    for (a, b) in [
+        (compressed, uncompressed),
+        (compressed, other),
+        (uncompressed, other),
+        (other, other),
+    ] {
+        assert_eq!(a.point_eq(&b), a == b);
+        assert_eq!(bool::from(a.ct_eq(&b)), a == b);
+        assert_eq!(a.point_eq(&b), b.point_eq(&a));
+    }
+    assert!(compressed.point_eq(&uncompressed));
+    assert!(!compressed.point_eq(&other));
+}
+
+#[test]
+fn contains_ct() {
+    use p256::elliptic_curve::{contains_ct, subtle::ConstantTimeEq};
+
+    let set: Vec<_> = (1..=4)
+        .map(|n| PublicKey::from_secret_scalar(&scalar(n)))
+        .collect();
+
+    for key in &set {
+        assert!(bool::from(contains_ct(&set, key)));
+        assert!(bool::from(key.ct_eq(key)));
+    }
+    let other = PublicKey::from_secret_scalar(&scalar(5));
+    assert!(!bool::from(contains_ct(&set, &other)));
+    assert!(!bool::from(other.ct_eq(&set[0])));
+    assert!(!bool::from(contains_ct(&[], &other)));
+}
+
+#[test]
+fn hash_agrees_with_eq() {
+    use std::collections::HashSet;
+
+    let compressed = PublicKey::from_sec1_bytes(COMPRESSED_BASEPOINT).unwrap();
+    let uncompressed =
+        PublicKey::from_sec1_bytes(compressed.to_encoded_point(false).as_bytes()).unwrap();
+    let keys: HashSet<_> = [
+        compressed,
+        uncompressed,
+        PublicKey::from_secret_scalar(&scalar(2)),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(keys.len(), 2);
+    assert!(keys.contains(&PublicKey::from_secret_scalar(&scalar(1))));
+}
+
+#[test]
+fn negate() {
+    let k = scalar(42);
+    let public_key = PublicKey::from_secret_scalar(&k);
+    let negated = public_key.negate();
+
+    assert_eq!(negated, PublicKey::from_secret_scalar(&-k));
+    assert_ne!(negated, public_key);
+    assert_eq!(negated.negate(), public_key);
+
+    let (point, negated_point) = (
+        public_key.to_encoded_point(false),
+        negated.to_encoded_point(false),
+    );
+    assert_eq!(point.x(), negated_point.x());
+    assert_ne!(point.y(), negated_point.y());
+}
+
+#[test]
+fn combine() {
+    let a = PublicKey::from_secret_scalar(&scalar(2));
+    let b = PublicKey::from_secret_scalar(&scalar(40));
+    assert_eq!(a.combine(&b).unwrap(), b.combine(&a).unwrap());
+    assert_eq!(
+        a.combine(&b).unwrap(),
+        PublicKey::from_secret_scalar(&scalar(42))
+    );
+    assert_eq!(
+        a.combine(&a).unwrap(),
+        PublicKey::from_secret_scalar(&scalar(4))
+    );
+    assert!(a.combine(&a.negate()).is_err());
+}
+
+#[test]
+fn mul_scalar() {
+    let public_key = PublicKey::from_secret_scalar(&scalar(21));
+    assert_eq!(public_key.mul_scalar(&scalar(1)), public_key);
+    assert_eq!(
+        public_key.mul_scalar(&scalar(2)),
+        PublicKey::from_secret_scalar(&scalar(42))
+    );
+    assert_eq!(
+        PublicKey::from_secret_scalar(&scalar(1)).mul_scalar(&scalar(42)),
+        PublicKey::from_secret_scalar(&scalar(42))
+    );
+    assert_eq!(public_key.mul_scalar(&-scalar(1)), public_key.negate());
+}
+
+#[test]
+fn commit() {
+    let h = PublicKey::from_secret_scalar(&scalar(1234));
+    let (v1, b1, v2, b2) = (scalar(5), scalar(6), scalar(7), scalar(8));
+
+    let c1 = PublicKey::commit(&v1, &b1, &h).unwrap();
+    let expected = PublicKey::from_secret_scalar(&v1).to_projective() + h.to_projective() * *b1;
+    assert_eq!(c1.to_projective(), expected);
+
+    // Commitments are additively homomorphic.
+    let c2 = PublicKey::commit(&v2, &b2, &h).unwrap();
+    let sum = PublicKey::commit(&scalar(12), &scalar(14), &h).unwrap();
+    assert_eq!(c1.to_projective() + c2.to_projective(), sum.to_projective());
+
+    // 1×G + (-1)×G is the identity.
+    let g = PublicKey::from_secret_scalar(&scalar(1));
+    assert!(PublicKey::commit(&scalar(1), &-scalar(1), &g).is_err());
+}
+
+#[test]
+fn from_sec1_bytes_reporting() {
+    use p256::elliptic_curve::PointEncodingKind;
+
+    let public_key = PublicKey::from_secret_scalar(&scalar(42));
+    let compressed = public_key.to_encoded_point(true);
+    let uncompressed = public_key.to_encoded_point(false);
+
+    let mut hybrid = uncompressed.as_bytes().to_vec();
+    hybrid[0] = 0x06 | (compressed.as_bytes()[0] & 1);
+    let mut wrong_parity = hybrid.clone();
+    wrong_parity[0] ^= 1;
+
+    for (bytes, kind) in [
+        (compressed.as_bytes(), PointEncodingKind::Compressed),
+        (uncompressed.as_bytes(), PointEncodingKind::Uncompressed),
+        (&hybrid[..], PointEncodingKind::Hybrid),
+    ] {
+        assert_eq!(
+            PublicKey::from_sec1_bytes_reporting(bytes).unwrap(),
+            (public_key, kind)
+        );
+    }
+
+    assert!(PublicKey::from_sec1_bytes_reporting(&wrong_parity).is_err());
+    assert!(PublicKey::from_sec1_bytes_reporting(&hybrid[..33]).is_err());
+    assert!(PublicKey::from_sec1_bytes_reporting(&[0x00]).is_err());
+    assert!(PublicKey::from_sec1_bytes_reporting(&[]).is_err());
+}
+
+#[test]
+fn accumulator_sums_terms() {
+    use p256::elliptic_curve::PublicKeyAccumulator;
+
+    // 2×G + 3×(5×G) = 17×G
+    let p = PublicKey::from_secret_scalar(&scalar(5));
+    let sum = PublicKeyAccumulator::new()
+        .add_generator_term(&Scalar::from(2u64))
+        .add_term(&Scalar::from(3u64), &p)
+        .finalize()
+        .unwrap();
+    assert_eq!(sum, PublicKey::from_secret_scalar(&scalar(17)));
+
+    assert!(PublicKeyAccumulator::<p256::NistP256>::new()
+        .finalize()
+        .is_err());
+    assert!(PublicKeyAccumulator::new()
+        .add_term(&Scalar::from(5u64), &p)
+        .add_generator_term(&-Scalar::from(25u64))
+        .finalize()
+        .is_err());
+}
+
+#[test]
+fn tls_point_round_trip() {
+    let public_key = PublicKey::from_secret_scalar(&scalar(42));
+    let tls_point = public_key.to_tls_point();
+    assert_eq!(tls_point.len(), 65);
+    assert_eq!(tls_point[0], 0x04);
+    assert_eq!(PublicKey::from_tls_point(&tls_point).unwrap(), public_key);
+}
+
+#[test]
+fn from_tls_point_rejects_compressed_and_identity() {
+    assert!(PublicKey::from_sec1_bytes(COMPRESSED_BASEPOINT).is_ok());
+    assert!(PublicKey::from_tls_point(COMPRESSED_BASEPOINT).is_err());
+    assert!(PublicKey::from_tls_point(&[0x00]).is_err());
+    assert!(PublicKey::from_tls_point(&[]).is_err());
+
+    let mut truncated = PublicKey::from_secret_scalar(&scalar(42)).to_tls_point();
+    truncated.pop();
+    assert!(PublicKey::from_tls_point(&truncated).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn wire_round_trip() {
+    let keys = [
+        PublicKey::from_secret_scalar(&scalar(1)),
+        PublicKey::from_secret_scalar(&scalar(42)),
+    ];
+
+    for compress in [true, false] {
+        let mut stream = Vec::new();
+        for key in &keys {
+            stream.extend(key.to_wire(compress));
+        }
+        assert_eq!(stream.len(), 2 * if compress { 34 } else { 66 });
+        assert_eq!(stream[0], if compress { 33 } else { 65 });
+
+        let (first, rest) = PublicKey::from_wire(&stream).unwrap();
+        let (second, rest) = PublicKey::from_wire(rest).unwrap();
+        assert_eq!([first, second], keys);
+        assert!(rest.is_empty());
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn from_wire_rejects_truncated_input() {
+    let wire = PublicKey::from_secret_scalar(&scalar(42)).to_wire(true);
+    assert!(PublicKey::from_wire(&[]).is_err());
+    assert!(PublicKey::from_wire(&wire[..wire.len() - 1]).is_err());
+    assert!(PublicKey::from_wire(&[0]).is_err());
+}
+
+#[cfg(feature = "ring-interop")]
+#[test]
+fn ring_uncompressed_round_trip() {
+    let public_key = PublicKey::from_secret_scalar(&scalar(42));
+    let bytes = public_key.to_ring_uncompressed();
+    assert_eq!(bytes.len(), 65);
+    assert_eq!(bytes[0], 0x04);
+    assert_eq!(bytes, public_key.to_encoded_point(false).as_bytes());
+    assert_eq!(
+        PublicKey::from_ring_uncompressed(&bytes).unwrap(),
+        public_key
+    );
+
+    assert!(PublicKey::from_ring_uncompressed(COMPRESSED_BASEPOINT).is_err());
+    assert!(PublicKey::from_ring_uncompressed(&bytes[..64]).is_err());
+    assert!(PublicKey::from_ring_uncompressed(&[0x00]).is_err());
+    assert!(PublicKey::from_ring_uncompressed(&[]).is_err());
+
+    let mut off_curve = bytes;
+    off_curve[64] ^= 1;
+    assert!(PublicKey::from_ring_uncompressed(&off_curve).is_err());
+}
+
+#[cfg(feature = "pkcs8")]
+#[test]
+fn csr_spki_matches_public_key_der() {
+    use p256::pkcs8::{der::Encode, EncodePublicKey};
+
+    let public_key = PublicKey::from_secret_scalar(&scalar(42));
+    let spki = public_key.to_csr_spki().unwrap();
+    assert_eq!(spki.algorithm.oid, p256::elliptic_curve::ALGORITHM_OID);
+    assert_eq!(
+        spki.subject_public_key.raw_bytes(),
+        public_key.to_tls_point().as_slice()
+    );
+    assert_eq!(
+        spki.to_der().unwrap(),
+        public_key.to_public_key_der().unwrap().as_bytes()
+    );
+}
+
+#[cfg(feature = "pkcs8")]
+#[test]
+fn public_key_der_with_oid() {
+    use p256::{
+        elliptic_curve::ALGORITHM_OID,
+        pkcs8::{
+            spki::SubjectPublicKeyInfoRef, AssociatedOid, DecodePublicKey, EncodePublicKey,
+            ObjectIdentifier,
+        },
+        NistP256,
+    };
+
+    let public_key = PublicKey::from_secret_scalar(&scalar(42));
+    assert_eq!(
+        public_key
+            .to_public_key_der_with_oid(ALGORITHM_OID, NistP256::OID)
+            .unwrap(),
+        public_key.to_public_key_der().unwrap()
+    );
+
+    // id-ecDH (RFC 5480) with secp256r1
+    let ecdh = ObjectIdentifier::new_unwrap("1.3.132.1.12");
+    let der = public_key
+        .to_public_key_der_with_oid(ecdh, NistP256::OID)
+        .unwrap();
+    let spki = SubjectPublicKeyInfoRef::try_from(der.as_bytes()).unwrap();
+    assert_eq!(spki.algorithm.oid, ecdh);
+    assert_eq!(spki.algorithm.parameters_oid().unwrap(), NistP256::OID);
+    assert_eq!(
+        spki.subject_public_key.raw_bytes(),
+        public_key.to_tls_point().as_slice()
+    );
+    assert!(PublicKey::from_public_key_der(der.as_bytes()).is_err());
+}
+
+#[cfg(feature = "pkcs8")]
+#[test]
+fn public_key_der_is_minimal() {
+    use p256::pkcs8::EncodePublicKey;
+
+    // SEQUENCE {
+    //   SEQUENCE { OID id-ecPublicKey, OID secp256r1 }
+    //   BIT STRING { 0 unused bits, uncompressed point }
+    // }
+    const HEADER: [u8; 26] = hex!(
+        "3059"
+        "3013" "06072a8648ce3d0201" "06082a8648ce3d030107"
+        "034200"
+    );
+
+    for n in [1, 42, u64::MAX] {
+        let public_key = PublicKey::from_secret_scalar(&scalar(n));
+        let der = public_key.to_public_key_der().unwrap();
+        assert_eq!(der.as_bytes().len(), 91);
+        assert_eq!(&der.as_bytes()[..26], &HEADER);
+        assert_eq!(
+            &der.as_bytes()[26..],
+            public_key.to_encoded_point(false).as_bytes()
+        );
+    }
+}
+
+#[cfg(feature = "pkcs8")]
+#[test]
+fn length_prefixed_der_round_trip() {
+    use p256::pkcs8::EncodePublicKey;
+
+    let public_key = PublicKey::from_secret_scalar(&scalar(42));
+    let der = public_key.to_public_key_der().unwrap();
+    let framed = public_key.to_length_prefixed_der().unwrap();
+    assert_eq!(&framed[..2], &(der.as_bytes().len() as u16).to_be_bytes());
+    assert_eq!(&framed[2..], der.as_bytes());
+    assert_eq!(
+        PublicKey::from_length_prefixed_der(&framed).unwrap(),
+        public_key
+    );
+
+    let mut trailing = framed.clone();
+    trailing.push(0);
+    assert!(PublicKey::from_length_prefixed_der(&trailing).is_err());
+    assert!(PublicKey::from_length_prefixed_der(&framed[..framed.len() - 1]).is_err());
+    assert!(PublicKey::from_length_prefixed_der(&framed[..1]).is_err());
+    assert!(PublicKey::from_length_prefixed_der(&[]).is_err());
+}
+
+#[cfg(feature = "x509")]
+#[test]
+fn verifies_self_signed_certificate() {
+    use p256::ecdsa::{CertificateError, PublicKeyExt};
+
+    // Generated by OpenSSL for the key with secret scalar 42.
+    const CERT_SHA256: &[u8] = include_bytes!("examples/self-signed-cert-sha256.der");
+    const CERT_SHA384: &[u8] = include_bytes!("examples/self-signed-cert-sha384.der");
+    const CERT_ED25519: &[u8] = include_bytes!("examples/ed25519-cert.der");
+
+    let public_key = PublicKey::from_secret_scalar(&scalar(42));
+    assert_eq!(public_key.verifies_certificate(CERT_SHA256), Ok(true));
+    assert_eq!(public_key.verifies_certificate(CERT_SHA384), Ok(true));
+
+    let other_key = PublicKey::from_secret_scalar(&scalar(43));
+    assert_eq!(other_key.verifies_certificate(CERT_SHA256), Ok(false));
+
+    let subject = b"signature_test";
+    let offset = CERT_SHA256
+        .windows(subject.len())
+        .position(|window| window == subject)
+        .unwrap();
+    let mut tampered = CERT_SHA256.to_vec();
+    tampered[offset] ^= 1;
+    assert_eq!(public_key.verifies_certificate(&tampered), Ok(false));
+
+    assert!(matches!(
+        public_key.verifies_certificate(CERT_ED25519),
+        Err(CertificateError::SignatureAlgorithm(_))
+    ));
+    assert!(matches!(
+        public_key.verifies_certificate(&CERT_SHA256[..100]),
+        Err(CertificateError::Der(_))
+    ));
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn sec1_heapless_matches_sec1_bytes() {
+    use p256::elliptic_curve::MAX_SEC1_POINT_SIZE;
+
+    let public_key = PublicKey::from_secret_scalar(&scalar(42));
+    let sec1 = public_key
+        .to_sec1_heapless::<MAX_SEC1_POINT_SIZE>()
+        .unwrap();
+    assert_eq!(&sec1[..], &*public_key.to_sec1_bytes());
+    assert!(public_key.to_sec1_heapless::<65>().is_ok());
+    assert!(public_key.to_sec1_heapless::<64>().is_err());
+}
+
+#[cfg(feature = "jwk")]
+#[test]
+fn try_to_public_key() {
+    use p256::{
+        elliptic_curve::{JwkEcKey, JwkError},
+        EncodedPoint, FieldBytes, NistP256,
+    };
+
+    let public_key = PublicKey::from_secret_scalar(&scalar(1));
+    let jwk = public_key.to_jwk();
+    assert_eq!(jwk.try_to_public_key::<NistP256>(), Ok(public_key));
+
+    let jwk_from = |x: &FieldBytes, y: &FieldBytes| {
+        let point = EncodedPoint::from_affine_coordinates(x, y, false);
+        JwkEcKey::from_encoded_point::<NistP256>(&point).unwrap()
+    };
+    let point = public_key.to_encoded_point(false);
+    let (x, y) = (point.x().unwrap(), point.y().unwrap());
+
+    let mut tampered = *y;
+    tampered[31] ^= 1;
+    let tampered = jwk_from(x, &tampered);
+    assert_eq!(
+        tampered.try_to_public_key::<NistP256>(),
+        Err(JwkError::NotOnCurve)
+    );
+    assert!(tampered.to_public_key::<NistP256>().is_err());
+
+    let unreduced = jwk_from(&FieldBytes::from([0xff; 32]), y);
+    assert_eq!(
+        unreduced.try_to_public_key::<NistP256>(),
+        Err(JwkError::Coordinate)
+    );
+
+    let json = jwk.to_string();
+    let y = json
+        .split(r#""y":""#)
+        .nth(1)
+        .unwrap()
+        .split('"')
+        .next()
+        .unwrap();
+    let truncated: JwkEcKey = json.replace(y, &y[..y.len() - 4]).parse().unwrap();
+    assert_eq!(
+        truncated.try_to_public_key::<NistP256>(),
+        Err(JwkError::Coordinate)
+    );
+
+    let other_curve: JwkEcKey = json.replace("P-256", "P-384").parse().unwrap();
+    assert_eq!(
+        other_curve.try_to_public_key::<NistP256>(),
+        Err(JwkError::Curve)
+    );
+}
+
+/// `ssh-keygen -y` output for the keys with secret scalars 1 and 42.
+#[cfg(feature = "openssh")]
+const OPENSSH_KEYS: [(u64, &str); 2] = [
+    (1, "ecdsa-sha2-nistp256 AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBGsX0fLhLEJH+Lzm5WOkQPJ3A32BLeszoPShOUXYmMKWT+NC4v4af5uO5+tKfA+eFivOM1drMV7Oy7ZAaDe/UfU="),
+    (42, "ecdsa-sha2-nistp256 AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBGeAxfxwJ14scGGg54d7sXTereuYhwJ/P6g2VBWLp/UMPLqMNLw10g6B9zCsHHvW1mGpQvkMapylXFEvnkoAEmY="),
+];
+
+#[cfg(feature = "openssh")]
+#[test]
+fn to_openssh_matches_ssh_keygen() {
+    for (n, expected) in OPENSSH_KEYS {
+        let public_key = PublicKey::from_secret_scalar(&scalar(n));
+        assert_eq!(public_key.to_openssh().unwrap(), expected);
+    }
+}
+
+#[cfg(feature = "openssh")]
+#[test]
+fn from_openssh_round_trip() {
+    for (n, line) in OPENSSH_KEYS {
+        let public_key = PublicKey::from_secret_scalar(&scalar(n));
+        assert_eq!(PublicKey::from_openssh(line).unwrap(), public_key);
+        assert_eq!(
+            PublicKey::from_openssh(&format!("{line} user@host")).unwrap(),
+            public_key
+        );
+    }
+}
+
+#[cfg(feature = "openssh")]
+#[test]
+fn from_openssh_rejects_other_curves() {
+    const P384_KEY: &str = "ecdsa-sha2-nistp384 AAAAE2VjZHNhLXNoYTItbmlzdHAzODQAAAAIbmlzdHAzODQAAABhBFZcXYTlxr2wDqu/GGR8f2k1DeY0nZPjkEiRwWwuXeXt97oPgy9UvQDkJCTU0xs+PHSNFI3ghIJzRoamqajGGRx8selAuF15w95Z2/is3OdKZhk6BUJ19y59gsCFx9gktA==";
+    assert!(PublicKey::from_openssh(P384_KEY).is_err());
+
+    // A P-384 blob relabelled as P-256 is caught by the blob's own key type.
+    let relabelled = P384_KEY.replacen("nistp384", "nistp256", 1);
+    assert!(PublicKey::from_openssh(&relabelled).is_err());
+}
@@ -2,7 +2,7 @@
 
 #![allow(clippy::needless_range_loop, clippy::op_ref)]
 
-use crate::{point_arithmetic::PointArithmetic, AffinePoint, Field, PrimeCurveParams};
+use crate::{point_arithmetic::PointArithmetic, AffinePoint, Field, PrimeCurveParams, PrimeField};
 use core::{
     borrow::Borrow,
     iter::Sum,
@@ -18,7 +18,7 @@ use elliptic_curve::{
         Group, GroupEncoding,
     },
     ops::{LinearCombination, MulByGenerator},
-    point::Double,
+    point::{Double, FromProjectiveBytes},
     rand_core::RngCore,
     sec1::{
         CompressedPoint, EncodedPoint, FromEncodedPoint, ModulusSize, ToEncodedPoint,
@@ -262,6 +262,45 @@ where
     }
 }
 
+/// Decodes `X || Y || Z`, each coordinate being the field element's canonical
+/// big-endian encoding (not its internal, e.g. Montgomery, representation),
+/// where the affine point is `(X/Z, Y/Z)`.
+impl<C> FromProjectiveBytes for ProjectivePoint<C>
+where
+    C: PrimeCurveParams,
+{
+    fn from_projective_bytes(bytes: &[u8]) -> CtOption<Self> {
+        let size = FieldBytes::<C>::default().len();
+        if bytes.len() != 3 * size {
+            return CtOption::new(Self::IDENTITY, Choice::from(0));
+        }
+
+        let coordinate = |i: usize| {
+            let mut repr = FieldBytes::<C>::default();
+            repr.copy_from_slice(&bytes[i * size..(i + 1) * size]);
+            C::FieldElement::from_repr(repr)
+        };
+
+        coordinate(0).and_then(|x| {
+            coordinate(1).and_then(|y| {
+                coordinate(2).and_then(|z| {
+                    // Homogeneous curve equation: Y²Z = X³ + aXZ² + bZ³
+                    let z2 = z.square();
+                    let rhs =
+                        x.square() * &x + &(C::EQUATION_A * &x * &z2) + &(C::EQUATION_B * &z2 * &z);
+                    let on_curve = (y.square() * &z).ct_eq(&rhs);
+
+                    // (0 : 1 : 0) is the only valid point with Z = 0, and the
+                    // equation alone would also accept (0 : 0 : 0).
+                    let not_degenerate = !z.is_zero() | (x.is_zero() & !y.is_zero());
+
+                    CtOption::new(Self { x, y, z }, on_curve & not_degenerate)
+                })
+            })
+        })
+    }
+}
+
 impl<C> Group for ProjectivePoint<C>
 where
     Self: Double,
@@ -30,6 +30,17 @@ pub enum Error {
         /// Unrecognized OID value found in e.g. a SPKI `AlgorithmIdentifier`.
         oid: ObjectIdentifier,
     },
+
+    /// PEM document has the wrong type label, e.g. `CERTIFICATE` where a
+    /// `PUBLIC KEY` was expected.
+    #[cfg(feature = "pem")]
+    UnexpectedPemLabel {
+        /// Label of the PEM document which was decoded.
+        found: FoundLabel,
+
+        /// Label which was expected.
+        expected: &'static str,
+    },
 }
 
 impl fmt::Display for Error {
@@ -43,6 +54,14 @@ impl fmt::Display for Error {
             Error::OidUnknown { oid } => {
                 write!(f, "unknown/unsupported algorithm OID: {}", oid)
             }
+            #[cfg(feature = "pem")]
+            Error::UnexpectedPemLabel { found, expected } => {
+                write!(
+                    f,
+                    "unexpected PEM label: found \"{}\", expected \"{}\"",
+                    found, expected
+                )
+            }
         }
     }
 }
@@ -66,3 +85,57 @@ impl From<pem::Error> for Error {
 
 #[cfg(feature = "std")]
 impl std::error::Error for Error {}
+
+/// PEM type label found in a document, as reported by
+/// [`Error::UnexpectedPemLabel`].
+///
+/// The label is stored inline so that [`Error`] remains `Copy`. Labels
+/// longer than [`FoundLabel::MAX_LEN`] bytes are truncated.
+#[cfg(feature = "pem")]
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct FoundLabel {
+    bytes: [u8; FoundLabel::MAX_LEN],
+    len: u8,
+}
+
+#[cfg(feature = "pem")]
+impl FoundLabel {
+    /// Maximum length of a stored label in bytes.
+    pub const MAX_LEN: usize = 32;
+
+    /// Store `label`, truncating it to at most [`FoundLabel::MAX_LEN`] bytes
+    /// on a character boundary.
+    pub fn new(label: &str) -> Self {
+        let mut len = label.len().min(Self::MAX_LEN);
+        while !label.is_char_boundary(len) {
+            len -= 1;
+        }
+
+        let mut bytes = [0; Self::MAX_LEN];
+        bytes[..len].copy_from_slice(&label.as_bytes()[..len]);
+        Self {
+            bytes,
+            len: len as u8,
+        }
+    }
+
+    /// Borrow the stored label.
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..usize::from(self.len)])
+            .expect("label was truncated on a character boundary")
+    }
+}
+
+#[cfg(feature = "pem")]
+impl fmt::Debug for FoundLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(feature = "pem")]
+impl fmt::Display for FoundLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
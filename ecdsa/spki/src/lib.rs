@@ -65,3 +65,6 @@ pub use {
 
 #[cfg(feature = "fingerprint")]
 pub use crate::fingerprint::FingerprintBytes;
+
+#[cfg(feature = "pem")]
+pub use crate::error::FoundLabel;
@@ -1,18 +1,28 @@
 //! Traits for encoding/decoding SPKI public keys.
 
-use crate::{Error, Result, SubjectPublicKeyInfoRef};
+use crate::{AlgorithmIdentifierRef, Error, Result, SubjectPublicKeyInfoRef};
 
 #[cfg(feature = "alloc")]
 use der::Document;
 
 #[cfg(feature = "pem")]
 use {
-    alloc::string::String,
-    der::pem::{LineEnding, PemLabel},
+    crate::FoundLabel,
+    alloc::{string::String, vec, vec::Vec},
+    der::pem::{self, LineEnding, PemLabel},
 };
 
 #[cfg(feature = "std")]
-use std::path::Path;
+use std::{io, path::Path};
+
+#[cfg(all(feature = "pem", feature = "std"))]
+use std::fs;
+
+#[cfg(all(feature = "alloc", feature = "fingerprint"))]
+use {
+    crate::{fingerprint, FingerprintBytes},
+    der::Writer,
+};
 
 #[cfg(doc)]
 use crate::SubjectPublicKeyInfo;
@@ -23,6 +33,18 @@ pub trait DecodePublicKey: Sized {
     /// (binary format).
     fn from_public_key_der(bytes: &[u8]) -> Result<Self>;
 
+    /// Deserialize object from ASN.1 DER-encoded [`SubjectPublicKeyInfo`],
+    /// also returning its [`AlgorithmIdentifierRef`] so callers can log or
+    /// branch on the OID and parameters.
+    ///
+    /// The algorithm is only returned if the key decodes. To inspect it when
+    /// decoding fails, e.g. for a key on the wrong curve, parse a
+    /// [`SubjectPublicKeyInfoRef`] directly.
+    fn from_public_key_der_with_alg(bytes: &[u8]) -> Result<(Self, AlgorithmIdentifierRef<'_>)> {
+        let algorithm = SubjectPublicKeyInfoRef::try_from(bytes)?.algorithm;
+        Ok((Self::from_public_key_der(bytes)?, algorithm))
+    }
+
     /// Deserialize PEM-encoded [`SubjectPublicKeyInfo`].
     ///
     /// Keys in this format begin with the following delimiter:
@@ -33,10 +55,43 @@ pub trait DecodePublicKey: Sized {
     #[cfg(feature = "pem")]
     fn from_public_key_pem(s: &str) -> Result<Self> {
         let (label, doc) = Document::from_pem(s)?;
-        SubjectPublicKeyInfoRef::validate_pem_label(label)?;
+        validate_pem_label(label)?;
         Self::from_public_key_der(doc.as_bytes())
     }
 
+    /// Deserialize every PEM-encoded [`SubjectPublicKeyInfo`] in a bundle of
+    /// concatenated PEM documents, in the order they appear.
+    ///
+    /// Documents with labels other than `PUBLIC KEY`, such as certificates,
+    /// are skipped. Text between documents is ignored.
+    #[cfg(feature = "pem")]
+    fn from_public_key_pem_bundle(s: &str) -> Result<Vec<Self>> {
+        const BEGIN: &str = "-----BEGIN ";
+        const END: &str = "-----END ";
+
+        let mut keys = Vec::new();
+        let mut rest = s;
+        while let Some(start) = rest.find(BEGIN) {
+            let section = &rest[start..];
+            let len = match section.find(END) {
+                Some(end) => section[end..]
+                    .find('\n')
+                    .map_or(section.len(), |eol| end + eol + 1),
+                None => section.len(),
+            };
+            let (section, tail) = section.split_at(len);
+            rest = tail;
+
+            let label = section[BEGIN.len()..]
+                .split_once("-----")
+                .map_or("", |(label, _)| label);
+            if label == SubjectPublicKeyInfoRef::PEM_LABEL {
+                keys.push(Self::from_public_key_pem(section)?);
+            }
+        }
+        Ok(keys)
+    }
+
     /// Load public key object from an ASN.1 DER-encoded file on the local
     /// filesystem (binary format).
     #[cfg(feature = "std")]
@@ -46,11 +101,17 @@ pub trait DecodePublicKey: Sized {
     }
 
     /// Load public key object from a PEM-encoded file on the local filesystem.
+    ///
+    /// Unlike [`DecodePublicKey::from_public_key_pem`], this tolerates a
+    /// UTF-8 byte order mark and any lines before the first
+    /// `-----BEGIN PUBLIC KEY-----` delimiter, such as comments added by the
+    /// tool which wrote the file.
     #[cfg(all(feature = "pem", feature = "std"))]
     fn read_public_key_pem_file(path: impl AsRef<Path>) -> Result<Self> {
-        let (label, doc) = Document::read_pem_file(path)?;
-        SubjectPublicKeyInfoRef::validate_pem_label(&label)?;
-        Self::from_public_key_der(doc.as_bytes())
+        let pem = fs::read_to_string(path).map_err(der::Error::from)?;
+        let pem = pem.strip_prefix('\u{feff}').unwrap_or(&pem);
+        let start = pem.find("-----BEGIN PUBLIC KEY-----").unwrap_or(0);
+        Self::from_public_key_pem(&pem[start..])
     }
 }
 
@@ -63,6 +124,16 @@ where
     }
 }
 
+/// Check that a PEM document holds a [`SubjectPublicKeyInfo`], reporting
+/// the label found otherwise.
+#[cfg(feature = "pem")]
+fn validate_pem_label(label: &str) -> Result<()> {
+    SubjectPublicKeyInfoRef::validate_pem_label(label).map_err(|_| Error::UnexpectedPemLabel {
+        found: FoundLabel::new(label),
+        expected: SubjectPublicKeyInfoRef::PEM_LABEL,
+    })
+}
+
 /// Serialize a public key object to a SPKI-encoded document.
 #[cfg(feature = "alloc")]
 pub trait EncodePublicKey {
@@ -70,10 +141,63 @@ pub trait EncodePublicKey {
     fn to_public_key_der(&self) -> Result<Document>;
 
     /// Serialize this public key as PEM-encoded SPKI with the given [`LineEnding`].
+    ///
+    /// The Base64 body is wrapped at 64 characters, as RFC 7468 requires.
     #[cfg(feature = "pem")]
     fn to_public_key_pem(&self, line_ending: LineEnding) -> Result<String> {
+        self.to_public_key_pem_with(line_ending, 64)
+    }
+
+    /// Serialize this public key as PEM-encoded SPKI with the given
+    /// [`LineEnding`], wrapping the Base64 body every `wrap` characters.
+    ///
+    /// This is for legacy parsers which expect e.g. 76-character lines. A
+    /// `wrap` at least as long as the body, such as `usize::MAX`, puts the
+    /// whole body on one line. Returns an error if `wrap` is zero.
+    #[cfg(feature = "pem")]
+    fn to_public_key_pem_with(&self, line_ending: LineEnding, wrap: usize) -> Result<String> {
+        if wrap == 0 {
+            return Err(pem::Error::Length.into());
+        }
+
+        let doc = self.to_public_key_der()?;
+        let label = SubjectPublicKeyInfoRef::PEM_LABEL;
+        let len = pem::encapsulated_len_wrapped(label, wrap, line_ending, doc.as_bytes().len())?;
+        let mut buf = vec![0u8; len];
+        let mut encoder = pem::Encoder::new_wrapped(label, wrap, line_ending, &mut buf)?;
+        encoder.encode(doc.as_bytes())?;
+        let written = encoder.finish()?;
+        buf.truncate(written);
+        String::from_utf8(buf).map_err(|_| pem::Error::CharacterEncoding.into())
+    }
+
+    /// Calculate the SHA-256 fingerprint of this public key's SPKI encoding,
+    /// e.g. for key pinning.
+    ///
+    /// This is the digest of [`EncodePublicKey::to_public_key_der`], as
+    /// computed by [`SubjectPublicKeyInfo::fingerprint_bytes`] and by
+    /// `openssl pkey -pubin -outform DER | openssl dgst -sha256`.
+    ///
+    /// See [RFC7469 § 2.1.1] for more information.
+    ///
+    /// [RFC7469 § 2.1.1]: https://datatracker.ietf.org/doc/html/rfc7469#section-2.1.1
+    #[cfg(feature = "fingerprint")]
+    fn to_public_key_fingerprint(&self) -> Result<FingerprintBytes> {
+        let mut builder = fingerprint::Builder::new();
+        builder.write(self.to_public_key_der()?.as_bytes())?;
+        Ok(builder.finish())
+    }
+
+    /// Write ASN.1 DER-encoded public key to the given writer, e.g. when
+    /// exporting many keys to one file or socket.
+    ///
+    /// The key is encoded once with [`EncodePublicKey::to_public_key_der`]
+    /// and its bytes written straight to `writer`.
+    #[cfg(feature = "std")]
+    fn write_public_key_der<W: io::Write>(&self, writer: &mut W) -> Result<()> {
         let doc = self.to_public_key_der()?;
-        Ok(doc.to_pem(SubjectPublicKeyInfoRef::PEM_LABEL, line_ending)?)
+        writer.write_all(doc.as_bytes()).map_err(der::Error::from)?;
+        Ok(())
     }
 
     /// Write ASN.1 DER-encoded public key to the given path
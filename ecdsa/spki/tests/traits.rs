@@ -11,6 +11,9 @@ use spki::der::pem::LineEnding;
 #[cfg(feature = "std")]
 use tempfile::tempdir;
 
+#[cfg(feature = "fingerprint")]
+use hex_literal::hex;
+
 #[cfg(all(feature = "pem", feature = "std"))]
 use std::fs;
 
@@ -21,6 +24,21 @@ const ED25519_DER_EXAMPLE: &[u8] = include_bytes!("examples/ed25519-pub.der");
 #[cfg(feature = "pem")]
 const ED25519_PEM_EXAMPLE: &str = include_str!("examples/ed25519-pub.pem");
 
+/// RSA-2048 `SubjectPublicKeyInfo` encoded as ASN.1 DER
+#[cfg(feature = "pem")]
+const RSA_2048_DER_EXAMPLE: &[u8] = include_bytes!("examples/rsa2048-pub.der");
+
+/// RSA-2048 public key encoded as PEM
+#[cfg(feature = "pem")]
+const RSA_2048_PEM_EXAMPLE: &str = include_str!("examples/rsa2048-pub.pem");
+
+/// SHA-256 digest of `ED25519_DER_EXAMPLE`
+///
+/// Generated using `cat ed25519-pub.der | openssl dgst -sha256`
+#[cfg(feature = "fingerprint")]
+const ED25519_SPKI_FINGERPRINT: [u8; 32] =
+    hex!("55dd4c74b0e48534e2f4e173ceceb50df8f27a7ac2aa8991cc7ae914e030bced");
+
 /// Mock key type for testing trait impls against.
 pub struct MockKey(Vec<u8>);
 
@@ -44,6 +62,14 @@ impl TryFrom<SubjectPublicKeyInfoRef<'_>> for MockKey {
     }
 }
 
+#[test]
+fn from_public_key_der_with_alg() {
+    let (key, algorithm) = MockKey::from_public_key_der_with_alg(ED25519_DER_EXAMPLE).unwrap();
+    assert_eq!(key.as_ref(), ED25519_DER_EXAMPLE);
+    assert_eq!(algorithm.oid, "1.3.101.112".parse().unwrap());
+    assert_eq!(algorithm.parameters, None);
+}
+
 #[cfg(feature = "pem")]
 #[test]
 fn from_public_key_pem() {
@@ -51,6 +77,37 @@ fn from_public_key_pem() {
     assert_eq!(key.as_ref(), ED25519_DER_EXAMPLE);
 }
 
+#[cfg(feature = "pem")]
+#[test]
+fn from_public_key_pem_bundle() {
+    let certificate = ED25519_PEM_EXAMPLE.replace("PUBLIC KEY", "CERTIFICATE");
+    let bundle = format!("{ED25519_PEM_EXAMPLE}{certificate}\n{RSA_2048_PEM_EXAMPLE}");
+
+    let keys = MockKey::from_public_key_pem_bundle(&bundle).unwrap();
+    assert_eq!(keys.len(), 2);
+    assert_eq!(keys[0].as_ref(), ED25519_DER_EXAMPLE);
+    assert_eq!(keys[1].as_ref(), RSA_2048_DER_EXAMPLE);
+}
+
+#[cfg(feature = "pem")]
+#[test]
+fn from_public_key_pem_unexpected_label() {
+    let pem = ED25519_PEM_EXAMPLE.replace("PUBLIC KEY", "CERTIFICATE");
+    let err = match MockKey::from_public_key_pem(&pem) {
+        Err(err) => err,
+        Ok(_) => panic!("decoded a CERTIFICATE as a PUBLIC KEY"),
+    };
+
+    assert!(matches!(
+        err,
+        Error::UnexpectedPemLabel { found, expected: "PUBLIC KEY" } if found.as_str() == "CERTIFICATE"
+    ));
+    assert_eq!(
+        err.to_string(),
+        "unexpected PEM label: found \"CERTIFICATE\", expected \"PUBLIC KEY\""
+    );
+}
+
 #[cfg(feature = "std")]
 #[test]
 fn read_public_key_der_file() {
@@ -65,6 +122,13 @@ fn read_public_key_pem_file() {
     assert_eq!(key.as_ref(), ED25519_DER_EXAMPLE);
 }
 
+#[cfg(all(feature = "pem", feature = "std"))]
+#[test]
+fn read_public_key_pem_file_with_preamble() {
+    let key = MockKey::read_public_key_pem_file("tests/examples/ed25519-pub-preamble.pem").unwrap();
+    assert_eq!(key.as_ref(), ED25519_DER_EXAMPLE);
+}
+
 #[cfg(feature = "pem")]
 #[test]
 fn to_public_key_pem() {
@@ -75,6 +139,51 @@ fn to_public_key_pem() {
     assert_eq!(pem, ED25519_PEM_EXAMPLE);
 }
 
+#[cfg(feature = "pem")]
+#[test]
+fn to_public_key_pem_with() {
+    let key = MockKey(RSA_2048_DER_EXAMPLE.to_vec());
+    assert_eq!(
+        key.to_public_key_pem_with(LineEnding::LF, 64).unwrap(),
+        RSA_2048_PEM_EXAMPLE
+    );
+
+    let body: String = RSA_2048_PEM_EXAMPLE
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect();
+    let unwrapped = key
+        .to_public_key_pem_with(LineEnding::LF, usize::MAX)
+        .unwrap();
+    assert_eq!(
+        unwrapped,
+        format!("-----BEGIN PUBLIC KEY-----\n{body}\n-----END PUBLIC KEY-----\n")
+    );
+
+    assert!(key.to_public_key_pem_with(LineEnding::LF, 0).is_err());
+}
+
+#[cfg(feature = "fingerprint")]
+#[test]
+fn to_public_key_fingerprint() {
+    let fingerprint = MockKey(ED25519_DER_EXAMPLE.to_vec())
+        .to_public_key_fingerprint()
+        .unwrap();
+
+    assert_eq!(fingerprint, ED25519_SPKI_FINGERPRINT);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn write_public_key_der() {
+    let key = MockKey(ED25519_DER_EXAMPLE.to_vec());
+    let mut out = Vec::new();
+    key.write_public_key_der(&mut out).unwrap();
+    key.write_public_key_der(&mut out).unwrap();
+
+    assert_eq!(out, [ED25519_DER_EXAMPLE, ED25519_DER_EXAMPLE].concat());
+}
+
 #[cfg(feature = "std")]
 #[test]
 fn write_public_key_der_file() {
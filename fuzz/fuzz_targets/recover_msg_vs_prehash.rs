@@ -0,0 +1,43 @@
+//! Differential fuzzing of `recover_from_msg` against `recover_from_prehash`.
+//!
+//! The first 32 bytes of the input are the secret key and the rest is the
+//! message. The message is signed, and the key is recovered through both
+//! entry points under every recovery ID. `recover_from_msg` hashes with
+//! SHA-256 and defers to the prehash path, so the two must agree exactly,
+//! failures included.
+//!
+//! Run with `cargo fuzz run recover_msg_vs_prehash` from this directory.
+//! Failing harness trials can be added to its corpus with
+//! `signature_test --save-corpus fuzz/corpus/recover_msg_vs_prehash`.
+
+#![no_main]
+
+use ecdsa::RecoveryId;
+use libfuzzer_sys::fuzz_target;
+use p256::ecdsa::{SigningKey, VerifyingKey};
+use sha2::{Digest, Sha256};
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 32 {
+        return;
+    }
+
+    let (secret, msg) = data.split_at(32);
+    let Ok(sk) = SigningKey::from_slice(secret) else {
+        return;
+    };
+    let (signature, _) = sk.sign_recoverable(msg).unwrap();
+    let prehash = Sha256::digest(msg);
+
+    for byte in 0..=RecoveryId::MAX {
+        let recovery_id = RecoveryId::from_byte(byte).unwrap();
+        let from_msg = VerifyingKey::recover_from_msg(msg, &signature, recovery_id).ok();
+        let from_prehash =
+            VerifyingKey::recover_from_prehash(&prehash, &signature, recovery_id).ok();
+
+        assert_eq!(
+            from_msg, from_prehash,
+            "recovery paths diverged for recovery id {byte}"
+        );
+    }
+});
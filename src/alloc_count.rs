@@ -0,0 +1,90 @@
+//! Heap allocation counting for the sign+recover cycle.
+//!
+//! With the `count-allocations` feature, a counting wrapper around the
+//! system allocator is installed as the global allocator and each [`Span`]
+//! adds the allocations made while it was open to a running total. Without
+//! the feature, [`Span`] is a zero-sized no-op and [`take`] returns `None`.
+
+#[cfg(feature = "count-allocations")]
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Allocations made by the process so far.
+#[cfg(feature = "count-allocations")]
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// Allocations recorded by finished [`Span`]s since the last [`take`].
+#[cfg(feature = "count-allocations")]
+static RECORDED: AtomicU64 = AtomicU64::new(0);
+
+/// [`System`], counting every allocation and reallocation.
+#[cfg(feature = "count-allocations")]
+struct CountingAllocator;
+
+#[cfg(feature = "count-allocations")]
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// An interval whose allocations are added to the running total.
+#[must_use = "a span only records allocations once finished"]
+pub struct Span {
+    #[cfg(feature = "count-allocations")]
+    start: u64,
+}
+
+impl Span {
+    /// Start counting.
+    #[inline]
+    pub fn start() -> Self {
+        Self {
+            #[cfg(feature = "count-allocations")]
+            start: ALLOCATIONS.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Stop counting and record the allocations made since [`Span::start`].
+    #[inline]
+    pub fn finish(self) {
+        #[cfg(feature = "count-allocations")]
+        RECORDED.fetch_add(
+            ALLOCATIONS.load(Ordering::Relaxed) - self.start,
+            Ordering::Relaxed,
+        );
+    }
+}
+
+/// Take the total recorded by every [`Span`] finished since the last call.
+#[cfg(feature = "count-allocations")]
+pub fn take() -> Option<u64> {
+    Some(RECORDED.swap(0, Ordering::Relaxed))
+}
+
+/// Allocation counting is disabled, so there is nothing to take.
+#[cfg(not(feature = "count-allocations"))]
+pub fn take() -> Option<u64> {
+    None
+}
@@ -0,0 +1,51 @@
+//! Public keys on any curve the harness supports.
+//!
+//! [`AnyPublicKey`] is the dynamically dispatched counterpart of the
+//! per-curve `PublicKey<C>` types: keys of different curves can share one
+//! collection, and compare unequal rather than failing to type-check.
+
+use crate::config::CurveName;
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+
+/// A public key on one of the curves in [`CurveName`].
+///
+/// Keys on different curves are never equal; keys on the same curve are
+/// equal if their points are.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum AnyPublicKey {
+    /// A NIST P-256 key.
+    P256(p256::PublicKey),
+}
+
+impl AnyPublicKey {
+    /// Curve the key is on.
+    pub fn curve(self) -> CurveName {
+        match self {
+            Self::P256(_) => CurveName::P256,
+        }
+    }
+
+    /// SEC1 encoding of the key's point.
+    pub fn to_sec1_bytes(self, compress: bool) -> Vec<u8> {
+        match self {
+            Self::P256(key) => key.to_encoded_point(compress).as_bytes().to_vec(),
+        }
+    }
+}
+
+impl From<p256::PublicKey> for AnyPublicKey {
+    fn from(key: p256::PublicKey) -> Self {
+        Self::P256(key)
+    }
+}
+
+/// Fails with the original key if it is on another curve.
+impl TryFrom<AnyPublicKey> for p256::PublicKey {
+    type Error = AnyPublicKey;
+
+    fn try_from(key: AnyPublicKey) -> Result<Self, AnyPublicKey> {
+        match key {
+            AnyPublicKey::P256(key) => Ok(key),
+        }
+    }
+}
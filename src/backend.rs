@@ -0,0 +1,107 @@
+//! Interchangeable implementations of the operations under test.
+//!
+//! Each [`Backend`] performs the same public-key operations on the same
+//! inputs and reports the raw bytes it produced, so two builds of the
+//! arithmetic can be diffed against each other.
+//!
+//! Only one copy of `p256`/`elliptic-curve` is vendored today. To compare
+//! against another version, add it under a renamed dependency, e.g.
+//!
+//! ```toml
+//! p256_alt = { package = "p256", version = "=0.13.2" }
+//! ```
+//!
+//! then copy [`VendoredP256`] for the new crate and append it to
+//! [`BACKENDS`].
+
+use p256::{
+    ecdsa::{SigningKey, VerifyingKey},
+    elliptic_curve::sec1::ToEncodedPoint,
+};
+
+/// Backends compared by `--compare-backends`. The first entry is the
+/// baseline every other backend is diffed against.
+pub const BACKENDS: &[&dyn Backend] = &[&VendoredP256];
+
+/// Bytes a backend produced for one input.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Observations {
+    /// Compressed SEC1 encoding of the public key.
+    pub public_key_compressed: Vec<u8>,
+
+    /// Uncompressed SEC1 encoding of the public key.
+    pub public_key_uncompressed: Vec<u8>,
+
+    /// Fixed-size `r || s` encoding of the recoverable signature.
+    pub signature: Vec<u8>,
+
+    /// Recovery ID returned alongside the signature.
+    pub recovery_id: u8,
+
+    /// Uncompressed SEC1 encoding of the key recovered from the signature,
+    /// or `None` if recovery failed.
+    pub recovered_key: Option<Vec<u8>>,
+}
+
+impl Observations {
+    /// Names of the fields which differ between `self` and `other`.
+    pub fn diff(&self, other: &Self) -> Vec<&'static str> {
+        let mut fields = Vec::new();
+
+        if self.public_key_compressed != other.public_key_compressed {
+            fields.push("public_key_compressed");
+        }
+        if self.public_key_uncompressed != other.public_key_uncompressed {
+            fields.push("public_key_uncompressed");
+        }
+        if self.signature != other.signature {
+            fields.push("signature");
+        }
+        if self.recovery_id != other.recovery_id {
+            fields.push("recovery_id");
+        }
+        if self.recovered_key != other.recovered_key {
+            fields.push("recovered_key");
+        }
+
+        fields
+    }
+}
+
+/// A set of curve implementations exercised by the harness.
+pub trait Backend: Sync {
+    /// Human-readable name used in reports.
+    fn name(&self) -> &'static str;
+
+    /// Derive a key from `secret` (a big-endian scalar), sign `msg` with it,
+    /// and recover the key from the signature.
+    ///
+    /// Returns `None` if `secret` is not a valid secret key for this backend.
+    fn observe(&self, secret: &[u8], msg: &[u8]) -> Option<Observations>;
+}
+
+/// The vendored `p256` crate and its `elliptic-curve`/`ecdsa` dependencies.
+pub struct VendoredP256;
+
+impl Backend for VendoredP256 {
+    fn name(&self) -> &'static str {
+        "p256 (vendored)"
+    }
+
+    fn observe(&self, secret: &[u8], msg: &[u8]) -> Option<Observations> {
+        let sk = SigningKey::from_slice(secret).ok()?;
+        let pk = sk.verifying_key().as_affine();
+        let (signature, recovery_id) = sk.sign_recoverable(msg).ok()?;
+        let recovered_key = VerifyingKey::recover_from_msg(msg, &signature, recovery_id)
+            .ok()
+            .map(|vk| vk.to_encoded_point(false).as_bytes().to_vec());
+
+        Some(Observations {
+            public_key_compressed: pk.to_encoded_point(true).as_bytes().to_vec(),
+            public_key_uncompressed: pk.to_encoded_point(false).as_bytes().to_vec(),
+            signature: signature.to_bytes().to_vec(),
+            recovery_id: recovery_id.to_byte(),
+            recovered_key,
+        })
+    }
+}
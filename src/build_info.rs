@@ -0,0 +1,91 @@
+//! Build metadata captured by `build.rs`, printed by `--version`.
+//!
+//! The `Cargo.lock` checksum identifies the upstream release a vendored crate
+//! was taken from; local patches to it are only captured by the harness'
+//! own [`GIT_REVISION`] and [`GIT_DIRTY`] flag.
+
+use crate::json_string;
+use std::fmt::Write as _;
+
+/// A resolved dependency as recorded in `Cargo.lock`.
+#[derive(Debug)]
+pub struct Dependency {
+    /// Package name.
+    pub name: &'static str,
+
+    /// Resolved version, or `"unknown"` if the package wasn't locked.
+    pub version: &'static str,
+
+    /// Package source, e.g. the crates.io registry or a git repository.
+    pub source: Option<&'static str>,
+
+    /// Commit the package was resolved to, for git sources.
+    pub revision: Option<&'static str>,
+
+    /// Checksum of the registry package.
+    pub checksum: Option<&'static str>,
+}
+
+include!(concat!(env!("OUT_DIR"), "/build_info.rs"));
+
+/// Name and version of the harness itself.
+pub const PACKAGE: &str = concat!(env!("CARGO_PKG_NAME"), " ", env!("CARGO_PKG_VERSION"));
+
+/// Human-readable build metadata.
+pub fn text() -> String {
+    let mut out = String::from(PACKAGE);
+    if let Some(revision) = GIT_REVISION {
+        let _ = write!(
+            out,
+            " (git {revision}{})",
+            if GIT_DIRTY { ", dirty" } else { "" }
+        );
+    }
+    out.push('\n');
+
+    for dep in DEPENDENCIES {
+        let _ = write!(out, "{} {}", dep.name, dep.version);
+        if let Some(source) = dep.source {
+            let _ = write!(out, " ({source}");
+            if let Some(revision) = dep.revision {
+                let _ = write!(out, ", rev {revision}");
+            }
+            if let Some(checksum) = dep.checksum {
+                let _ = write!(out, ", checksum {checksum}");
+            }
+            out.push(')');
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Build metadata as a single JSON object.
+pub fn json() -> String {
+    let opt = |s: Option<&str>| s.map_or_else(|| "null".to_owned(), json_string);
+
+    let dependencies = DEPENDENCIES
+        .iter()
+        .map(|dep| {
+            format!(
+                "{{\"name\":{},\"version\":{},\"source\":{},\"revision\":{},\"checksum\":{}}}",
+                json_string(dep.name),
+                json_string(dep.version),
+                opt(dep.source),
+                opt(dep.revision),
+                opt(dep.checksum)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!(
+        "{{\"name\":{},\"version\":{},\"git_revision\":{},\"git_dirty\":{},\"dependencies\":[{}]}}",
+        json_string(env!("CARGO_PKG_NAME")),
+        json_string(env!("CARGO_PKG_VERSION")),
+        opt(GIT_REVISION),
+        GIT_DIRTY,
+        dependencies
+    )
+}
@@ -0,0 +1,188 @@
+//! Concurrent trials sharing one RNG.
+//!
+//! `--threads N --shared-rng` runs each (curve, message) pair's trials on `N` threads,
+//! which draw iterations from a shared counter and keys from a single
+//! mutex-guarded RNG. Single-threaded, every mismatch is the other 𝑹
+//! candidate and keys never repeat, so anything else points at a
+//! concurrency problem in the signing or recovery path or in the RNG
+//! sharing itself.
+
+use crate::{
+    any_key::AnyPublicKey,
+    config::{Config, CurveName, Format},
+    diagnostics::MismatchCounts,
+    json_string, p256_trial,
+    rng::{self, SharedRng},
+};
+use signature_test::Hashing;
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+/// Trials run by one thread.
+#[derive(Default)]
+struct ThreadSummary {
+    trials: u64,
+    failed: u64,
+    mismatches: MismatchCounts,
+    keys: Vec<AnyPublicKey>,
+}
+
+impl ThreadSummary {
+    /// Mismatches of a kind single-threaded runs never produce.
+    fn unexpected(&self) -> u64 {
+        self.mismatches.negation + self.mismatches.unrelated
+    }
+}
+
+/// Run every (curve, message) pair on `config.threads` threads and print
+/// the results. Returns the number of anomalies observed: duplicate keys,
+/// unexpected mismatches, and lost trials.
+pub fn run(config: &Config) -> u64 {
+    let mut anomalies = 0;
+
+    for &curve in &config.curves {
+        for message in &config.messages {
+            let shared = Mutex::new(rng::for_seed(config.seed));
+            let next = AtomicU64::new(0);
+
+            let threads: Vec<ThreadSummary> = thread::scope(|scope| {
+                let handles: Vec<_> = (0..config.threads)
+                    .map(|_| {
+                        scope.spawn(|| {
+                            let mut rng = SharedRng(&shared);
+                            let mut summary = ThreadSummary::default();
+                            while next.fetch_add(1, Ordering::Relaxed) < config.iterations {
+                                let trial = match curve {
+                                    CurveName::P256 => {
+                                        p256_trial(&mut rng, message, Hashing::Message, config)
+                                    }
+                                };
+                                summary.trials += 1;
+                                if !trial.matched {
+                                    summary.failed += 1;
+                                }
+                                if let Some(kind) = trial.mismatch {
+                                    summary.mismatches.record(kind);
+                                }
+                                summary.keys.push(trial.public_key);
+                            }
+                            summary
+                        })
+                    })
+                    .collect();
+
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().expect("trial thread panicked"))
+                    .collect()
+            });
+
+            let mut keys = HashSet::new();
+            let duplicate_keys = threads
+                .iter()
+                .flat_map(|thread| &thread.keys)
+                .filter(|key| !keys.insert(*key))
+                .count() as u64;
+            let trials: u64 = threads.iter().map(|thread| thread.trials).sum();
+            let lost = config.iterations.abs_diff(trials);
+            let unexpected: u64 = threads.iter().map(ThreadSummary::unexpected).sum();
+            anomalies += duplicate_keys + lost + unexpected;
+
+            print_concurrent(curve, message, config, &threads, duplicate_keys, lost);
+        }
+    }
+
+    anomalies
+}
+
+fn print_concurrent(
+    curve: CurveName,
+    message: &[u8],
+    config: &Config,
+    threads: &[ThreadSummary],
+    duplicate_keys: u64,
+    lost: u64,
+) {
+    let trials: u64 = threads.iter().map(|thread| thread.trials).sum();
+    let failed: u64 = threads.iter().map(|thread| thread.failed).sum();
+    let rate = failed as f64 / trials.max(1) as f64;
+
+    match config.format {
+        Format::Json => {
+            let per_thread: Vec<_> = threads
+                .iter()
+                .enumerate()
+                .map(|(id, thread)| {
+                    format!(
+                        "{{\"thread\":{},\"trials\":{},\"failures\":{},\"mismatches\":{{\"other_candidate\":{},\"negation\":{},\"unrelated\":{}}}}}",
+                        id,
+                        thread.trials,
+                        thread.failed,
+                        thread.mismatches.other_candidate,
+                        thread.mismatches.negation,
+                        thread.mismatches.unrelated
+                    )
+                })
+                .collect();
+            println!(
+                "{{\"curve\":\"{}\",\"message\":{},\"threads\":{},\"iterations\":{},\"trials\":{},\"failures\":{},\"failure_rate\":{},\"duplicate_keys\":{},\"per_thread\":[{}]}}",
+                curve,
+                json_string(&String::from_utf8_lossy(message)),
+                config.threads,
+                config.iterations,
+                trials,
+                failed,
+                rate,
+                duplicate_keys,
+                per_thread.join(",")
+            );
+        }
+        Format::Text | Format::OpenMetrics | Format::Tap => {
+            println!(
+                "Concurrent recovery ({} {:?}, {} threads sharing one RNG):",
+                curve,
+                String::from_utf8_lossy(message),
+                config.threads
+            );
+            for (id, thread) in threads.iter().enumerate() {
+                println!(
+                    "  thread {id}: {} trials, {} failures ({} from the other R candidate, {} negated, {} unrelated)",
+                    thread.trials,
+                    thread.failed,
+                    thread.mismatches.other_candidate,
+                    thread.mismatches.negation,
+                    thread.mismatches.unrelated
+                );
+            }
+            println!(
+                "Failure rate: {}/{} = {:.02}%",
+                failed,
+                trials,
+                rate * 100.0
+            );
+            for (id, thread) in threads.iter().enumerate() {
+                if thread.unexpected() > 0 {
+                    println!(
+                        "Thread {id} observed {} mismatches other than the other R candidate",
+                        thread.unexpected()
+                    );
+                }
+            }
+            if duplicate_keys > 0 {
+                println!("Duplicate keys across threads: {duplicate_keys}");
+            }
+            if lost > 0 {
+                println!(
+                    "Trials run: {trials}, expected {} (the iteration counter raced)",
+                    config.iterations
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,984 @@
+//! Harness configuration: command-line flags and TOML experiment files.
+//!
+//! Settings are resolved in three layers, each overriding the previous one:
+//!
+//! 1. built-in defaults
+//! 2. the TOML file passed via `--config FILE`
+//! 3. individual command-line flags
+//!
+//! # Config file schema
+//!
+//! ```toml
+//! curves = ["p256"]          # curves to exercise, in order
+//! iterations = 1000          # trials per (curve, message) pair
+//! messages = ["hello"]       # messages to sign
+//! seed = 42                  # optional: deterministic RNG seed
+//! format = "text"            # "text", "json", "openmetrics", or "tap"
+//! entropy = ["os", "chacha"] # optional: entropy sources to compare
+//! ```
+//!
+//! All keys are optional. Only the flat subset of TOML used above is
+//! understood: `key = value` pairs with integer, string, or single-line
+//! string-array values, plus `#` comments.
+
+use std::{
+    fmt, fs,
+    io::{self, Read as _},
+    path::PathBuf,
+    str::FromStr,
+};
+
+/// Usage summary printed by `--help`.
+pub const USAGE: &str = "\
+usage: signature_test [OPTIONS]
+       signature_test pubkey (--privkey-pem FILE | --privkey-hex HEX) [--format FORMAT]
+       signature_test replay FILE
+
+options:
+  --config FILE       read settings from a TOML file (flags override it)
+  --curve NAME        curve to exercise (repeatable): p256
+  --iterations N      trials per curve and message [default: 10]
+  --message STRING    message to sign (repeatable) [default: hello]
+  --message-file FILE sign the contents of FILE (repeatable); can't be
+                      combined with --message or --stdin
+  --stdin             sign all of standard input as a single message; can't
+                      be combined with --message or --message-file
+  --seed N            seed the RNG for reproducible runs
+  --entropy SOURCE    entropy source (repeatable): os, drbg, chacha, fixed;
+                      with several, results are compared side by side
+                      [default: drbg with --seed, os otherwise]
+  --format FORMAT     output format: text, json, openmetrics, tap
+                      [default: text]
+  --tap               shorthand for --format tap: one TAP test point per
+                      trial, exiting 1 if any fails
+  --prehashed         also run every message through the prehash APIs,
+                      hashing it with SHA-256 first
+  --raw-prehash       also run every message through the prehash APIs as
+                      is; each message must be as long as the curve's
+                      field (32 bytes for p256)
+  --erc2098           round-trip each signature through the 64-byte
+                      ERC-2098 compact encoding before recovering
+  --corrupt-recovery-id
+                      flip the recovery ID's parity bit before recovering
+                      and report how often the signer's key still results
+  --check-recovery-id report every recovery ID of 2 or 3 (x-coordinate
+                      reduced mod n) along with the input that produced it
+  --predict-failure-rate
+                      compare the observed failure rate with the rate
+                      predicted by the high-s parity hypothesis
+  --analyze-nonce     reconstruct each signature's nonce k and test whether
+                      failures depend on its top bit, k > n/2, or the
+                      parity and x-overflow of R = k×G
+  --timing            report throughput and the mean time spent signing and
+                      recovering per trial
+  --reference-mult    also derive each key with a naive double-and-add
+                      implementation independent of the p256 crate, and
+                      report when it disagrees with the library's key or
+                      the recovered key
+  --flamegraph FILE   write folded stacks of the time spent generating keys,
+                      signing, and recovering to FILE, for inferno-flamegraph
+                      or flamegraph.pl (requires the `profiling` feature)
+  --save-corpus DIR   write each failing trial into DIR as an input for the
+                      recover_msg_vs_prehash fuzz target
+  --dump-failures FILE
+                      append each failing trial to FILE as a line of JSON:
+                      a replay reproducer plus the expected and recovered
+                      public keys
+  --fail-fast         stop at the first failing trial, dumping its message,
+                      signature, recovery ID, and both keys in hex; can't be
+                      combined with --corrupt-recovery-id
+  --compare-backends  diff key derivation, signing, and recovery across
+                      every built-in backend; exits 2 if only one is built
+  --output-keys       print N freshly generated public keys (compressed
+                      hex, one per line) instead of testing recovery
+  --threads N         split each run's trials across N threads, each with
+                      its own RNG seeded with the seed plus the thread's
+                      index; allocations aren't counted with more than one
+                      [default: 1]
+  --shared-rng        have the --threads threads share one mutex-guarded
+                      RNG instead, and report each thread's results along
+                      with any duplicate keys or unexpected mismatches;
+                      exits 1 if there are any
+  --length-sweep      run N trials with random messages of each of the
+                      lengths 1, 4, 16, 64, 256, 1024, and 4096 bytes, and
+                      regress the failure rate on ln(length)
+  --version           print the harness and RustCrypto crate versions it
+                      was built against (honours --format)
+  -h, --help          print this help
+
+exit status:
+  0 if every trial recovered the signer's key (with --corrupt-recovery-id,
+  if none did), 1 otherwise, and 2 for invalid arguments
+
+replay:
+  Rerun the recoveries captured in FILE (`-` reads stdin), one reproducer
+  JSON object per line: either one printed on stderr for a mismatch, or a
+  whole --dump-failures file. Reports whether each still fails to recover a
+  key which matches the signer's and verifies, and exits 1 if any do.
+
+pubkey options:
+  --privkey-pem FILE  PKCS#8 (or SEC1) PEM private key; `-` reads stdin
+  --privkey-hex HEX   raw big-endian private key scalar; `-` reads stdin
+  --format FORMAT     public key format: pem, der, sec1, jwk [default: pem]
+";
+
+/// Curves supported by the harness.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CurveName {
+    /// NIST P-256 a.k.a. secp256r1.
+    P256,
+}
+
+impl CurveName {
+    /// Name of the curve as accepted on the command line.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::P256 => "p256",
+        }
+    }
+
+    /// Size of the curve's base field elements in bytes, which is the
+    /// required length of a prehash.
+    pub fn field_size(self) -> usize {
+        match self {
+            Self::P256 => 32,
+        }
+    }
+}
+
+impl FromStr for CurveName {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "p256" => Ok(Self::P256),
+            _ => Err(format!("unsupported curve: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for CurveName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Sources of randomness for key generation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Entropy {
+    /// The operating system RNG.
+    Os,
+
+    /// HMAC-DRBG seeded from `--seed`.
+    Drbg,
+
+    /// ChaCha20 keystream seeded from `--seed`.
+    ChaCha,
+
+    /// A constant byte pattern.
+    Fixed,
+}
+
+impl Entropy {
+    /// Name of the source as accepted on the command line.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Os => "os",
+            Self::Drbg => "drbg",
+            Self::ChaCha => "chacha",
+            Self::Fixed => "fixed",
+        }
+    }
+}
+
+impl FromStr for Entropy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "os" => Ok(Self::Os),
+            "drbg" => Ok(Self::Drbg),
+            "chacha" => Ok(Self::ChaCha),
+            "fixed" => Ok(Self::Fixed),
+            _ => Err(format!("unsupported entropy source: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for Entropy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Summary output formats.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Format {
+    /// Human-readable text.
+    #[default]
+    Text,
+
+    /// One JSON object per run.
+    Json,
+
+    /// An OpenMetrics exposition covering every run.
+    OpenMetrics,
+
+    /// One TAP test point per trial.
+    Tap,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "openmetrics" => Ok(Self::OpenMetrics),
+            "tap" => Ok(Self::Tap),
+            _ => Err(format!("unsupported format: {s}")),
+        }
+    }
+}
+
+/// Public key encodings supported by the `pubkey` command.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum KeyFormat {
+    /// PEM-encoded SPKI document.
+    #[default]
+    Pem,
+
+    /// Binary DER-encoded SPKI.
+    Der,
+
+    /// Compressed SEC1 point, as hex.
+    Sec1,
+
+    /// JSON Web Key.
+    Jwk,
+}
+
+impl FromStr for KeyFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "pem" => Ok(Self::Pem),
+            "der" => Ok(Self::Der),
+            "sec1" => Ok(Self::Sec1),
+            "jwk" => Ok(Self::Jwk),
+            _ => Err(format!("unsupported key format: {s}")),
+        }
+    }
+}
+
+/// Where the `pubkey` command reads its private key from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum KeyInput {
+    /// Path to a PEM file, or `-` for stdin.
+    Pem(String),
+
+    /// Hex-encoded scalar, or `-` to read it from stdin.
+    Hex(String),
+}
+
+/// Options for the `pubkey` command.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PubkeyOptions {
+    /// Private key to derive the public key from.
+    pub input: KeyInput,
+
+    /// Output encoding.
+    pub format: KeyFormat,
+}
+
+/// What the harness should do.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Mode {
+    /// Sign, recover, and compare against the signing key.
+    #[default]
+    Recovery,
+
+    /// Diff the output of every [`crate::backend::BACKENDS`] entry.
+    CompareBackends,
+
+    /// Print `iterations` freshly generated public keys per curve.
+    OutputKeys,
+
+    /// Regress the failure rate on the length of random messages.
+    LengthSweep,
+
+    /// Run trials on several threads sharing one RNG.
+    Concurrent,
+}
+
+/// Fully resolved harness configuration.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    /// Curves to exercise, in order.
+    pub curves: Vec<CurveName>,
+
+    /// Number of trials per (curve, message) pair.
+    pub iterations: u64,
+
+    /// Threads to split each run's trials between.
+    pub threads: usize,
+
+    /// Messages to sign.
+    pub messages: Vec<Vec<u8>>,
+
+    /// RNG seed; `None` uses the operating system RNG.
+    pub seed: Option<u64>,
+
+    /// Entropy sources to run every (curve, message) pair under. Empty means
+    /// the RNG is chosen by [`Config::seed`] alone.
+    pub entropy: Vec<Entropy>,
+
+    /// Summary output format.
+    pub format: Format,
+
+    /// What to run.
+    pub mode: Mode,
+
+    /// Additionally run each message through the prehash APIs, hashed with
+    /// SHA-256 first.
+    pub prehashed: bool,
+
+    /// Additionally run each message through the prehash APIs unhashed.
+    pub raw_prehash: bool,
+
+    /// Round-trip signatures through the ERC-2098 compact encoding.
+    pub erc2098: bool,
+
+    /// Flip the recovery ID's y-parity bit before recovering.
+    pub corrupt_recovery_id: bool,
+
+    /// Report recovery IDs with the x-reduced bit set.
+    pub check_recovery_id: bool,
+
+    /// Compare the observed failure rate against
+    /// [`crate::diagnostics::S_HIGH_HYPOTHESIS`].
+    pub predict_failure_rate: bool,
+
+    /// Correlate failures with properties of the signing nonce.
+    pub analyze_nonce: bool,
+
+    /// Cross-check keys against [`crate::reference::mul_base`].
+    pub reference_mult: bool,
+
+    /// Report throughput and per-phase timings.
+    pub timing: bool,
+
+    /// Fuzz corpus directory failing trials are written to.
+    pub save_corpus: Option<PathBuf>,
+
+    /// JSON Lines file failing trials are appended to.
+    pub dump_failures: Option<PathBuf>,
+
+    /// Stop at the first failing trial instead of counting failures.
+    pub fail_fast: bool,
+
+    /// File the folded-stack profile is written to.
+    pub flamegraph: Option<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            curves: vec![CurveName::P256],
+            iterations: 10,
+            threads: 1,
+            messages: vec![b"hello".to_vec()],
+            seed: None,
+            entropy: Vec::new(),
+            format: Format::Text,
+            mode: Mode::Recovery,
+            prehashed: false,
+            raw_prehash: false,
+            erc2098: false,
+            corrupt_recovery_id: false,
+            check_recovery_id: false,
+            predict_failure_rate: false,
+            analyze_nonce: false,
+            reference_mult: false,
+            timing: false,
+            save_corpus: None,
+            dump_failures: None,
+            fail_fast: false,
+            flamegraph: None,
+        }
+    }
+}
+
+/// Result of parsing the command line.
+#[derive(Debug)]
+pub enum Command {
+    /// Run the harness with the given configuration.
+    Run(Config),
+
+    /// Print usage and exit.
+    Help,
+
+    /// Print build metadata in the given format and exit.
+    Version(Format),
+
+    /// Derive and print the public key of a private key.
+    Pubkey(PubkeyOptions),
+
+    /// Rerun every reproducer in the JSON Lines file at the given path.
+    Replay(String),
+}
+
+/// Settings which may be given either in a config file or on the command
+/// line. Unset fields fall through to the next layer.
+#[derive(Debug, Default, Eq, PartialEq)]
+struct Overrides {
+    curves: Option<Vec<CurveName>>,
+    iterations: Option<u64>,
+    messages: Option<Vec<Vec<u8>>>,
+    seed: Option<u64>,
+    entropy: Option<Vec<Entropy>>,
+    format: Option<Format>,
+}
+
+impl Overrides {
+    fn apply(self, config: &mut Config) {
+        if let Some(curves) = self.curves {
+            config.curves = curves;
+        }
+        if let Some(iterations) = self.iterations {
+            config.iterations = iterations;
+        }
+        if let Some(messages) = self.messages {
+            config.messages = messages;
+        }
+        if self.seed.is_some() {
+            config.seed = self.seed;
+        }
+        if let Some(entropy) = self.entropy {
+            config.entropy = entropy;
+        }
+        if let Some(format) = self.format {
+            config.format = format;
+        }
+    }
+}
+
+impl Command {
+    /// Parse the process' command-line arguments.
+    pub fn from_env() -> Result<Self, String> {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    /// Parse the given command-line arguments (excluding the program name).
+    pub fn parse<I>(args: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut args = args.into_iter().peekable();
+        if args.peek().map(String::as_str) == Some("pubkey") {
+            args.next();
+            return Self::parse_pubkey(args);
+        }
+        if args.peek().map(String::as_str) == Some("replay") {
+            args.next();
+            return Self::parse_replay(args);
+        }
+
+        let mut config_path = None;
+        let mut flags = Overrides::default();
+        let mut message_source = None;
+        let mut mode = Mode::default();
+        let mut threads = None;
+        let mut version = false;
+        let mut prehashed = false;
+        let mut raw_prehash = false;
+        let mut erc2098 = false;
+        let mut corrupt_recovery_id = false;
+        let mut check_recovery_id = false;
+        let mut predict_failure_rate = false;
+        let mut analyze_nonce = false;
+        let mut reference_mult = false;
+        let mut timing = false;
+        let mut save_corpus = None;
+        let mut dump_failures = None;
+        let mut fail_fast = false;
+        let mut flamegraph = None;
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {arg}"))
+            };
+
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(Self::Help),
+                "--config" => config_path = Some(value()?),
+                "--curve" => flags
+                    .curves
+                    .get_or_insert_with(Vec::new)
+                    .push(value()?.parse()?),
+                "--iterations" => flags.iterations = Some(parse_int("--iterations", &value()?)?),
+                "--message" => {
+                    set_message_source(&mut message_source, "--message")?;
+                    flags
+                        .messages
+                        .get_or_insert_with(Vec::new)
+                        .push(value()?.into_bytes());
+                }
+                "--message-file" => {
+                    set_message_source(&mut message_source, "--message-file")?;
+                    let path = value()?;
+                    let message = fs::read(&path).map_err(|e| format!("{path}: {e}"))?;
+                    flags.messages.get_or_insert_with(Vec::new).push(message);
+                }
+                "--stdin" => {
+                    if message_source == Some("--stdin") {
+                        return Err("--stdin given more than once".to_owned());
+                    }
+                    set_message_source(&mut message_source, "--stdin")?;
+                    let mut message = Vec::new();
+                    io::stdin()
+                        .read_to_end(&mut message)
+                        .map_err(|e| format!("stdin: {e}"))?;
+                    flags.messages = Some(vec![message]);
+                }
+                "--seed" => flags.seed = Some(parse_int("--seed", &value()?)?),
+                "--entropy" => flags
+                    .entropy
+                    .get_or_insert_with(Vec::new)
+                    .push(value()?.parse()?),
+                "--format" => flags.format = Some(value()?.parse()?),
+                "--tap" => flags.format = Some(Format::Tap),
+                "--prehashed" => prehashed = true,
+                "--raw-prehash" => raw_prehash = true,
+                "--erc2098" => erc2098 = true,
+                "--corrupt-recovery-id" => corrupt_recovery_id = true,
+                "--check-recovery-id" => check_recovery_id = true,
+                "--predict-failure-rate" => predict_failure_rate = true,
+                "--analyze-nonce" => analyze_nonce = true,
+                "--reference-mult" => reference_mult = true,
+                "--timing" => timing = true,
+                "--save-corpus" => save_corpus = Some(PathBuf::from(value()?)),
+                "--dump-failures" => dump_failures = Some(PathBuf::from(value()?)),
+                "--fail-fast" => fail_fast = true,
+                "--flamegraph" => flamegraph = Some(PathBuf::from(value()?)),
+                "--compare-backends" => mode = Mode::CompareBackends,
+                "--output-keys" => mode = Mode::OutputKeys,
+                "--length-sweep" => mode = Mode::LengthSweep,
+                "--threads" => {
+                    let n = parse_int("--threads", &value()?)?;
+                    if n == 0 {
+                        return Err("--threads must be at least 1".to_owned());
+                    }
+                    threads = Some(usize::try_from(n).map_err(|_| "--threads is too large")?);
+                }
+                "--shared-rng" => mode = Mode::Concurrent,
+                "--version" => version = true,
+                _ => return Err(format!("unrecognized argument: {arg}")),
+            }
+        }
+
+        let mut config = Config::default();
+
+        if let Some(path) = config_path {
+            let toml = fs::read_to_string(&path).map_err(|e| format!("{path}: {e}"))?;
+            parse_toml(&toml)
+                .map_err(|e| format!("{path}: {e}"))?
+                .apply(&mut config);
+        }
+
+        flags.apply(&mut config);
+        config.mode = mode;
+        if let Some(threads) = threads {
+            config.threads = threads;
+        }
+        config.prehashed = prehashed;
+        config.raw_prehash = raw_prehash;
+        config.erc2098 = erc2098;
+        config.corrupt_recovery_id = corrupt_recovery_id;
+        config.check_recovery_id = check_recovery_id;
+        config.predict_failure_rate = predict_failure_rate;
+        config.analyze_nonce = analyze_nonce;
+        config.reference_mult = reference_mult;
+        config.timing = timing;
+        config.save_corpus = save_corpus;
+        config.dump_failures = dump_failures;
+        config.fail_fast = fail_fast;
+        config.flamegraph = flamegraph;
+
+        if config.fail_fast && config.corrupt_recovery_id {
+            return Err("--fail-fast can't be combined with --corrupt-recovery-id, where every trial is expected to fail".to_owned());
+        }
+
+        if config.flamegraph.is_some() && config.threads > 1 {
+            return Err("--flamegraph only profiles the main thread, so it can't be combined with --threads".to_owned());
+        }
+
+        if config.raw_prehash {
+            for &curve in &config.curves {
+                for message in &config.messages {
+                    if message.len() != curve.field_size() {
+                        return Err(format!(
+                            "--raw-prehash: {curve} prehashes must be {} bytes, but {:?} is {}",
+                            curve.field_size(),
+                            String::from_utf8_lossy(message),
+                            message.len()
+                        ));
+                    }
+                }
+            }
+        }
+
+        if version {
+            return Ok(Self::Version(config.format));
+        }
+
+        Ok(Self::Run(config))
+    }
+
+    /// Parse the arguments following `replay`.
+    fn parse_replay(args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut path = None;
+
+        for arg in args {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(Self::Help),
+                _ if path.is_none() && (arg == "-" || !arg.starts_with('-')) => path = Some(arg),
+                _ => return Err(format!("unrecognized argument: {arg}")),
+            }
+        }
+
+        path.map(Self::Replay)
+            .ok_or_else(|| "replay requires a reproducer file".to_owned())
+    }
+
+    /// Parse the arguments following `pubkey`.
+    fn parse_pubkey(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut input = None;
+        let mut format = KeyFormat::default();
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {arg}"))
+            };
+
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(Self::Help),
+                "--privkey-pem" => input = Some(KeyInput::Pem(value()?)),
+                "--privkey-hex" => input = Some(KeyInput::Hex(value()?)),
+                "--format" => format = value()?.parse()?,
+                _ => return Err(format!("unrecognized argument: {arg}")),
+            }
+        }
+
+        let input = input.ok_or("pubkey requires --privkey-pem or --privkey-hex")?;
+        Ok(Self::Pubkey(PubkeyOptions { input, format }))
+    }
+}
+
+/// Record that messages come from `flag`, failing if another flag already
+/// supplied some.
+fn set_message_source(source: &mut Option<&'static str>, flag: &'static str) -> Result<(), String> {
+    match *source {
+        Some(other) if other != flag => Err(format!("{flag} can't be combined with {other}")),
+        _ => {
+            *source = Some(flag);
+            Ok(())
+        }
+    }
+}
+
+fn parse_int(name: &str, s: &str) -> Result<u64, String> {
+    s.parse()
+        .map_err(|_| format!("invalid value for {name}: {s:?} (expected a non-negative integer)"))
+}
+
+/// Parse the flat TOML subset described in the module documentation.
+fn parse_toml(input: &str) -> Result<Overrides, String> {
+    let mut overrides = Overrides::default();
+
+    for (n, line) in input.lines().enumerate() {
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let err = |msg: &str| format!("line {}: {msg}", n + 1);
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| err("expected `key = value`"))?;
+        let value = value.trim();
+
+        match key.trim() {
+            "curves" => {
+                let curves = parse_string_array(value).map_err(|e| err(&e))?;
+                overrides.curves = Some(
+                    curves
+                        .iter()
+                        .map(|s| s.parse())
+                        .collect::<Result<_, _>>()
+                        .map_err(|e: String| err(&e))?,
+                );
+            }
+            "iterations" => {
+                overrides.iterations = Some(parse_int("iterations", value).map_err(|e| err(&e))?)
+            }
+            "messages" => {
+                let messages = parse_string_array(value).map_err(|e| err(&e))?;
+                overrides.messages = Some(messages.into_iter().map(String::into_bytes).collect());
+            }
+            "seed" => overrides.seed = Some(parse_int("seed", value).map_err(|e| err(&e))?),
+            "entropy" => {
+                let sources = parse_string_array(value).map_err(|e| err(&e))?;
+                overrides.entropy = Some(
+                    sources
+                        .iter()
+                        .map(|s| s.parse())
+                        .collect::<Result<_, _>>()
+                        .map_err(|e: String| err(&e))?,
+                );
+            }
+            "format" => {
+                let format = parse_string(value).map_err(|e| err(&e))?;
+                overrides.format = Some(format.parse().map_err(|e: String| err(&e))?);
+            }
+            other => return Err(err(&format!("unknown key `{other}`"))),
+        }
+    }
+
+    Ok(overrides)
+}
+
+/// Remove a trailing `#` comment, ignoring `#` inside string literals.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => (),
+        }
+    }
+
+    line
+}
+
+/// Parse a basic (double-quoted) TOML string.
+fn parse_string(value: &str) -> Result<String, String> {
+    let (s, rest) = parse_string_prefix(value)?;
+    if rest.trim().is_empty() {
+        Ok(s)
+    } else {
+        Err(format!("unexpected trailing characters: {rest}"))
+    }
+}
+
+/// Parse a basic string at the start of `value`, returning it along with the
+/// remaining input.
+fn parse_string_prefix(value: &str) -> Result<(String, &str), String> {
+    let body = value
+        .strip_prefix('"')
+        .ok_or_else(|| format!("expected a string, found: {value}"))?;
+    let mut out = String::new();
+    let mut chars = body.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((out, &body[i + 1..])),
+            '\\' => match chars.next().map(|(_, c)| c) {
+                Some('"') => out.push('"'),
+                Some('\\') => out.push('\\'),
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some(c) => return Err(format!("unsupported escape sequence: \\{c}")),
+                None => break,
+            },
+            c => out.push(c),
+        }
+    }
+
+    Err(format!("unterminated string: {value}"))
+}
+
+/// Parse a single-line array of basic strings.
+fn parse_string_array(value: &str) -> Result<Vec<String>, String> {
+    let mut rest = value
+        .strip_prefix('[')
+        .ok_or_else(|| format!("expected an array, found: {value}"))?
+        .trim_start();
+    let mut items = Vec::new();
+
+    loop {
+        if let Some(tail) = rest.strip_prefix(']') {
+            return if tail.trim().is_empty() {
+                Ok(items)
+            } else {
+                Err(format!("unexpected trailing characters: {tail}"))
+            };
+        }
+
+        let (item, tail) = parse_string_prefix(rest)?;
+        items.push(item);
+        rest = tail.trim_start();
+
+        if let Some(tail) = rest.strip_prefix(',') {
+            rest = tail.trim_start();
+        } else if !rest.starts_with(']') {
+            return Err(format!("expected `,` or `]`, found: {rest}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_comment() {
+        for (line, stripped) in [
+            ("seed = 1", "seed = 1"),
+            ("seed = 1 # comment", "seed = 1 "),
+            ("# whole line", ""),
+            (r#"format = "text" # "quoted""#, r#"format = "text" "#),
+            (r#"messages = ["a#b"]"#, r#"messages = ["a#b"]"#),
+            (
+                r##"messages = ["a\"#b"] # c"##,
+                r##"messages = ["a\"#b"] "##,
+            ),
+            (r#"messages = ["a\\"] # c"#, r#"messages = ["a\\"] "#),
+        ] {
+            assert_eq!(super::strip_comment(line), stripped, "{line}");
+        }
+    }
+
+    #[test]
+    fn parse_string() {
+        for (value, parsed) in [
+            (r#""""#, Ok(String::new())),
+            (r#""hello""#, Ok("hello".to_owned())),
+            (r#""hello"   "#, Ok("hello".to_owned())),
+            (r#""a # b""#, Ok("a # b".to_owned())),
+            (r#""q\"q""#, Ok("q\"q".to_owned())),
+            (r#""b\\s""#, Ok("b\\s".to_owned())),
+            (r#""n\nt\t""#, Ok("n\nt\t".to_owned())),
+            (
+                r#""\x""#,
+                Err("unsupported escape sequence: \\x".to_owned()),
+            ),
+            (r#""open"#, Err(r#"unterminated string: "open"#.to_owned())),
+            (
+                r#""trailing\"#,
+                Err(r#"unterminated string: "trailing\"#.to_owned()),
+            ),
+            ("bare", Err("expected a string, found: bare".to_owned())),
+            (
+                r#""a" "b""#,
+                Err(r#"unexpected trailing characters:  "b""#.to_owned()),
+            ),
+        ] {
+            assert_eq!(super::parse_string(value), parsed, "{value}");
+        }
+    }
+
+    #[test]
+    fn parse_string_array() {
+        let strings = |items: &[&str]| Ok(items.iter().map(|&s| s.to_owned()).collect());
+        for (value, parsed) in [
+            ("[]", strings(&[])),
+            ("[ ]", strings(&[])),
+            (r#"["a"]"#, strings(&["a"])),
+            (r#"["a", "b"]"#, strings(&["a", "b"])),
+            (r#"[ "a" , "b" , ]"#, strings(&["a", "b"])),
+            (r#"["a,b", "c]"]"#, strings(&["a,b", "c]"])),
+            (r#"["\"", "\\"]"#, strings(&["\"", "\\"])),
+            (r#""a""#, Err(r#"expected an array, found: "a""#.to_owned())),
+            (
+                r#"["a" "b"]"#,
+                Err(r#"expected `,` or `]`, found: "b"]"#.to_owned()),
+            ),
+            (
+                r#"["a"] x"#,
+                Err("unexpected trailing characters:  x".to_owned()),
+            ),
+            (r#"["a""#, Err("expected `,` or `]`, found: ".to_owned())),
+            ("[a]", Err("expected a string, found: a]".to_owned())),
+        ] {
+            assert_eq!(super::parse_string_array(value), parsed, "{value}");
+        }
+    }
+
+    #[test]
+    fn parse_toml() {
+        for (input, parsed) in [
+            ("", Overrides::default()),
+            ("# only a comment\n\n   \n", Overrides::default()),
+            (
+                "iterations = 10\nseed=7 # trailing comment\n",
+                Overrides {
+                    iterations: Some(10),
+                    seed: Some(7),
+                    ..Overrides::default()
+                },
+            ),
+            (
+                r#"curves = ["p256"]
+messages = ["hello", "with # hash", "esc\"aped\n"]
+entropy = ["os", "fixed"]
+format = "json""#,
+                Overrides {
+                    curves: Some(vec![CurveName::P256]),
+                    messages: Some(vec![
+                        b"hello".to_vec(),
+                        b"with # hash".to_vec(),
+                        b"esc\"aped\n".to_vec(),
+                    ]),
+                    entropy: Some(vec![Entropy::Os, Entropy::Fixed]),
+                    format: Some(Format::Json),
+                    ..Overrides::default()
+                },
+            ),
+        ] {
+            assert_eq!(super::parse_toml(input), Ok(parsed), "{input}");
+        }
+    }
+
+    #[test]
+    fn parse_toml_errors() {
+        for (input, error) in [
+            ("seed", "line 1: expected `key = value`"),
+            ("\n# comment\ncolour = 1", "line 3: unknown key `colour`"),
+            ("seed = 1\niterations = many", "line 2: "),
+            ("format = text", "line 1: expected a string, found: text"),
+            (
+                r#"format = "text" "json""#,
+                r#"line 1: unexpected trailing characters:  "json""#,
+            ),
+            (
+                "curves = [\"p256\"\n]",
+                "line 1: expected `,` or `]`, found: ",
+            ),
+            (
+                "\n\nmessages = [\"a\" # ]",
+                "line 3: expected `,` or `]`, found: ",
+            ),
+            (r#"entropy = ["os", "nope"]"#, "line 1: "),
+            (
+                r#"messages = ["\q"]"#,
+                "line 1: unsupported escape sequence: \\q",
+            ),
+        ] {
+            let parsed = super::parse_toml(input);
+            assert!(
+                parsed.as_ref().is_err_and(|e| e.starts_with(error)),
+                "{input:?}: {parsed:?}"
+            );
+        }
+    }
+}
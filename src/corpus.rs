@@ -0,0 +1,22 @@
+//! Export of failing trials into a fuzz corpus.
+//!
+//! Inputs use the layout decoded by `fuzz/fuzz_targets/recover_msg_vs_prehash.rs`:
+//! the 32-byte big-endian secret key followed by the message. Files are named
+//! by the SHA-256 of their contents, so saving the same case twice is a no-op.
+
+use crate::hex;
+use sha2::{Digest, Sha256};
+use std::{fs, io, path::Path};
+
+/// Encode a trial as a fuzz input.
+pub fn input(secret: &[u8; 32], msg: &[u8]) -> Vec<u8> {
+    [&secret[..], msg].concat()
+}
+
+/// Write the fuzz input for a trial into the corpus directory `dir`, creating
+/// it if needed.
+pub fn save(dir: &Path, secret: &[u8; 32], msg: &[u8]) -> io::Result<()> {
+    let input = input(secret, msg);
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join(hex(&Sha256::digest(&input))), input)
+}
@@ -0,0 +1,275 @@
+//! Analysis of failed recoveries.
+
+use ecdsa::RecoveryId;
+use p256::{
+    ecdsa::{Signature, VerifyingKey},
+    elliptic_curve::{
+        bigint::CheckedAdd,
+        ops::{Invert, Reduce},
+        point::{AffineCoordinates, DecompressPoint},
+        scalar::IsHigh,
+        sec1::ToEncodedPoint,
+        Curve, FieldBytesEncoding, PrimeField,
+    },
+    AffinePoint, FieldBytes, NistP256, ProjectivePoint, PublicKey, Scalar, U256,
+};
+use std::fmt::{self, Write as _};
+
+/// Comparison of the ephemeral point 𝑹 = 𝑘×𝑮 with the candidate point
+/// reconstructed from the signature's `r` and the recovery ID.
+#[derive(Debug)]
+pub struct RPointAnalysis {
+    /// Recovery ID under analysis.
+    pub recovery_id: RecoveryId,
+
+    /// Candidate 𝑹 lifted from `r` using the recovery ID, or `None` if `r`
+    /// is not the x-coordinate of any curve point.
+    pub candidate: Option<AffinePoint>,
+
+    /// Actual 𝑹, computed from the signer's key via the verification
+    /// equation 𝑹 = 𝑧𝑠⁻¹×𝑮 + 𝑟𝑠⁻¹×𝑸.
+    pub actual: AffinePoint,
+}
+
+impl RPointAnalysis {
+    /// Reconstruct both points for a signature produced by `signer` over
+    /// the 32-byte `prehash` of a message.
+    pub fn new(
+        signer: &VerifyingKey,
+        prehash: &[u8],
+        signature: &Signature,
+        recovery_id: RecoveryId,
+    ) -> Self {
+        let (r, s) = signature.split_scalars();
+        let z = <Scalar as Reduce<U256>>::reduce_bytes(FieldBytes::from_slice(prehash));
+
+        let s_inv = *s.invert();
+        let actual = (ProjectivePoint::GENERATOR * (z * s_inv)
+            + ProjectivePoint::from(*signer.as_affine()) * (*r * s_inv))
+            .to_affine();
+
+        let mut x = r.to_repr();
+        if recovery_id.is_x_reduced() {
+            x = Option::<U256>::from(U256::decode_field_bytes(&x).checked_add(&NistP256::ORDER))
+                .map(|x| x.encode_field_bytes())
+                .unwrap_or_default();
+        }
+
+        let candidate = AffinePoint::decompress(&x, u8::from(recovery_id.is_y_odd()).into()).into();
+
+        Self {
+            recovery_id,
+            candidate,
+            actual,
+        }
+    }
+
+    /// Is the actual 𝑹's y-coordinate odd?
+    pub fn actual_y_odd(&self) -> bool {
+        self.actual.y_is_odd().into()
+    }
+
+    /// Does the recovery ID's parity bit disagree with the actual 𝑹?
+    pub fn parity_mismatch(&self) -> bool {
+        self.recovery_id.is_y_odd() != self.actual_y_odd()
+    }
+}
+
+impl fmt::Display for RPointAnalysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parity = |odd: bool| if odd { "odd" } else { "even" };
+
+        match self.candidate {
+            Some(candidate) => write!(
+                f,
+                "candidate R is on the curve and {} the actual R",
+                if candidate == self.actual {
+                    "matches"
+                } else {
+                    "does not match"
+                }
+            )?,
+            None => f.write_str("candidate R is not a valid curve point")?,
+        }
+
+        write!(
+            f,
+            "; recovery id {} claims {} y, actual R has {} y",
+            self.recovery_id.to_byte(),
+            parity(self.recovery_id.is_y_odd()),
+            parity(self.actual_y_odd())
+        )?;
+
+        if self.parity_mismatch() {
+            f.write_str(" (parity mismatch)")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Hypothesis under test by `--predict-failure-rate`.
+///
+/// `ecdsa`'s signing computes the recovery ID's parity as 𝑹's y-parity XOR
+/// whether `s` is high, anticipating a low-`s` normalization which P-256
+/// signing never performs. If that is the whole bug, recovery fails exactly
+/// when `s > (n - 1) / 2`. With `s` uniform over `[1, n)`, that is exactly half
+/// of all trials.
+pub const S_HIGH_HYPOTHESIS: &str = "recovery ID parity is wrong exactly when s is high";
+
+/// Show the uncompressed SEC1 encodings of the `expected` and `recovered`
+/// keys one above the other, as the tag, 𝑥, and 𝑦 in hex, with a caret under
+/// the first byte that differs and a line naming the coordinate it's in.
+pub fn describe_difference(expected: &PublicKey, recovered: &PublicKey) -> String {
+    let expected = expected.to_encoded_point(false);
+    let recovered = recovered.to_encoded_point(false);
+    let (expected, recovered) = (expected.as_bytes(), recovered.as_bytes());
+
+    let mut out = String::new();
+    for (label, bytes) in [("expected: ", expected), ("recovered:", recovered)] {
+        let _ = writeln!(out, "{label} {}", spaced_hex(bytes));
+    }
+
+    let Some(first) = expected.iter().zip(recovered).position(|(a, b)| a != b) else {
+        out.push_str("keys are identical");
+        return out;
+    };
+    // Each byte is two hex digits, with a space after the tag and after 𝑥.
+    let column =
+        "recovered: ".len() + 2 * first + usize::from(first >= 1) + usize::from(first >= 33);
+    let location = match first {
+        0 => "the tag".to_owned(),
+        1..=32 => format!("x-coordinate byte {}", first - 1),
+        _ => format!("y-coordinate byte {}", first - 33),
+    };
+    let same_x = expected[1..33] == recovered[1..33];
+    let _ = write!(
+        out,
+        "{:column$}^\nfirst difference at byte {first} ({location}){}",
+        "",
+        if same_x {
+            "; x-coordinates are equal"
+        } else {
+            ""
+        }
+    );
+    out
+}
+
+/// Hex-encode an uncompressed SEC1 point as `tag x y`.
+fn spaced_hex(bytes: &[u8]) -> String {
+    let hex = |bytes: &[u8]| bytes.iter().map(|b| format!("{b:02x}")).collect::<String>();
+    format!(
+        "{} {} {}",
+        hex(&bytes[..1]),
+        hex(&bytes[1..33]),
+        hex(&bytes[33..])
+    )
+}
+
+/// Failure rate predicted by [`S_HIGH_HYPOTHESIS`].
+pub const S_HIGH_FAILURE_RATE: f64 = 0.5;
+
+/// Does [`S_HIGH_HYPOTHESIS`] predict that recovering `signature` fails?
+///
+/// Flipping the parity bit beforehand (`corrupt`) inverts the prediction.
+pub fn s_high_predicts_failure(signature: &Signature, corrupt: bool) -> bool {
+    bool::from(signature.s().is_high()) ^ corrupt
+}
+
+/// Is observing `failed` failures in `iterations` trials consistent with a
+/// failure probability `p`, within three standard deviations?
+pub fn rate_consistent(failed: u64, iterations: u64, p: f64) -> bool {
+    if iterations == 0 {
+        return true;
+    }
+    let n = iterations as f64;
+    let sigma = (p * (1.0 - p) / n).sqrt();
+    (failed as f64 / n - p).abs() <= 3.0 * sigma
+}
+
+/// How a wrongly recovered key relates to the signer's key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MismatchKind {
+    /// The key recovered from the other candidate 𝑹 sharing the
+    /// x-coordinate `r` (i.e. with the recovery ID's parity flipped) is the
+    /// signer's, so the wrong candidate was selected.
+    OtherCandidate,
+
+    /// The recovered key is the negation of the signer's key.
+    Negation,
+
+    /// Neither: the recovered key is unrelated to the signer's key.
+    Unrelated,
+}
+
+impl MismatchKind {
+    /// Classify a recovery of `recovered` from a signature over `prehash`,
+    /// where `signer` was expected.
+    pub fn classify(
+        signer: &VerifyingKey,
+        recovered: &VerifyingKey,
+        prehash: &[u8],
+        signature: &Signature,
+        recovery_id: RecoveryId,
+    ) -> Self {
+        let flipped = RecoveryId::new(!recovery_id.is_y_odd(), recovery_id.is_x_reduced());
+        let signer_pk = PublicKey::from(signer);
+
+        if VerifyingKey::recover_from_prehash(prehash, signature, flipped)
+            .ok()
+            .as_ref()
+            == Some(signer)
+        {
+            Self::OtherCandidate
+        } else if PublicKey::from(recovered) == signer_pk.negate() {
+            Self::Negation
+        } else {
+            Self::Unrelated
+        }
+    }
+
+    /// Short description used in reports.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::OtherCandidate => "other R candidate",
+            Self::Negation => "negation",
+            Self::Unrelated => "unrelated",
+        }
+    }
+}
+
+impl fmt::Display for MismatchKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Number of mismatches of each [`MismatchKind`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MismatchCounts {
+    /// [`MismatchKind::OtherCandidate`] mismatches.
+    pub other_candidate: u64,
+
+    /// [`MismatchKind::Negation`] mismatches.
+    pub negation: u64,
+
+    /// [`MismatchKind::Unrelated`] mismatches.
+    pub unrelated: u64,
+}
+
+impl MismatchCounts {
+    /// Count one mismatch of the given kind.
+    pub fn record(&mut self, kind: MismatchKind) {
+        match kind {
+            MismatchKind::OtherCandidate => self.other_candidate += 1,
+            MismatchKind::Negation => self.negation += 1,
+            MismatchKind::Unrelated => self.unrelated += 1,
+        }
+    }
+
+    /// Total number of classified mismatches.
+    pub fn total(&self) -> u64 {
+        self.other_candidate + self.negation + self.unrelated
+    }
+}
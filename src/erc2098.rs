@@ -0,0 +1,98 @@
+//! Compact 64-byte signatures as specified by [ERC-2098].
+//!
+//! The recovery ID's y-parity bit is folded into the otherwise unused high
+//! bit of a low-`s` signature's `s` value: `r || (v << 255 | s)`.
+//!
+//! [ERC-2098]: https://eips.ethereum.org/EIPS/eip-2098
+
+use ecdsa::RecoveryId;
+use p256::ecdsa::{signature::Error, Signature};
+
+/// Size of an ERC-2098 compact signature.
+pub const SIZE: usize = 64;
+
+/// Pack `signature` and `recovery_id` into an ERC-2098 compact signature.
+///
+/// A high `s` is normalized first, which negates 𝑹 and therefore flips the
+/// y-parity. Returns an error for recovery IDs with the x-reduced bit set,
+/// which ERC-2098 cannot represent.
+pub fn to_erc2098(signature: &Signature, recovery_id: RecoveryId) -> Result<[u8; SIZE], Error> {
+    if recovery_id.is_x_reduced() {
+        return Err(Error::new());
+    }
+
+    let (signature, y_odd) = match signature.normalize_s() {
+        Some(normalized) => (normalized, !recovery_id.is_y_odd()),
+        None => (*signature, recovery_id.is_y_odd()),
+    };
+
+    let mut bytes = [0u8; SIZE];
+    bytes.copy_from_slice(&signature.to_bytes());
+    debug_assert_eq!(bytes[32] & 0x80, 0, "low s has its high bit set");
+    bytes[32] |= u8::from(y_odd) << 7;
+    Ok(bytes)
+}
+
+/// Unpack an ERC-2098 compact signature into a signature and recovery ID.
+pub fn from_erc2098(bytes: &[u8; SIZE]) -> Result<(Signature, RecoveryId), Error> {
+    let y_odd = bytes[32] & 0x80 != 0;
+
+    let mut bytes = *bytes;
+    bytes[32] &= 0x7f;
+
+    let signature = Signature::from_slice(&bytes)?;
+    Ok((signature, RecoveryId::new(y_odd, false)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::ecdsa::SigningKey;
+
+    /// A low-`s` signature over `msg`.
+    fn low_s_signature(msg: &[u8]) -> Signature {
+        let signing_key = SigningKey::from_slice(&[7; 32]).unwrap();
+        let (signature, _) = signing_key.sign_recoverable(msg).unwrap();
+        signature.normalize_s().unwrap_or(signature)
+    }
+
+    #[test]
+    fn round_trips_both_parities() {
+        let signature = low_s_signature(b"hello");
+        for y_odd in [false, true] {
+            let recovery_id = RecoveryId::new(y_odd, false);
+            let compact = to_erc2098(&signature, recovery_id).unwrap();
+            assert_eq!(compact[32] >> 7, u8::from(y_odd));
+            assert_eq!(
+                from_erc2098(&compact).unwrap(),
+                (signature, recovery_id),
+                "y_odd = {y_odd}"
+            );
+        }
+    }
+
+    #[test]
+    fn normalizing_high_s_flips_parity() {
+        let low = low_s_signature(b"hello");
+        let (r, s) = low.split_scalars();
+        let high = Signature::from_scalars(r, -s).unwrap();
+        assert!(high.normalize_s().is_some());
+
+        for y_odd in [false, true] {
+            let compact = to_erc2098(&high, RecoveryId::new(y_odd, false)).unwrap();
+            assert_eq!(
+                from_erc2098(&compact).unwrap(),
+                (low, RecoveryId::new(!y_odd, false)),
+                "y_odd = {y_odd}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_x_reduced_recovery_ids() {
+        let signature = low_s_signature(b"hello");
+        for y_odd in [false, true] {
+            assert!(to_erc2098(&signature, RecoveryId::new(y_odd, true)).is_err());
+        }
+    }
+}
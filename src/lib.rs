@@ -0,0 +1,213 @@
+//! The sign+recover round trip exercised by the `signature_test` harness,
+//! for use from tests and other binaries.
+//!
+//! [`sign`] and [`recover`] are the two halves of every trial: the harness
+//! binary runs them for each iteration, with its diagnostics and
+//! flag-dependent transformations of the signature in between, and reruns
+//! captured failures through them with its `replay` command. [`recovery_trial`]
+//! strings them together without any of that.
+
+pub mod profiling;
+
+use ecdsa::RecoveryId;
+use p256::{
+    ecdsa::{
+        signature::{hazmat::PrehashVerifier, Verifier},
+        Signature, SigningKey, VerifyingKey,
+    },
+    elliptic_curve::rand_core::CryptoRngCore,
+};
+use sha2::{Digest, Sha256};
+use std::{
+    borrow::Cow,
+    fmt,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// How a trial's message is passed to the signing and recovery APIs.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Hashing {
+    /// Sign with `sign_recoverable` and recover with `recover_from_msg`,
+    /// which hash the message with SHA-256 themselves.
+    #[default]
+    Message,
+
+    /// Hash the message with SHA-256 once, then sign with
+    /// `sign_prehash_recoverable` and recover with `recover_from_prehash`.
+    Prehashed,
+
+    /// Pass the message to the prehash APIs as is, so it must already be a
+    /// prehash as long as the curve's field.
+    RawPrehash,
+}
+
+impl Hashing {
+    /// Name of the mode as used in reproducers.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Message => "message",
+            Self::Prehashed => "prehashed",
+            Self::RawPrehash => "raw-prehash",
+        }
+    }
+}
+
+impl FromStr for Hashing {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "message" => Ok(Self::Message),
+            "prehashed" => Ok(Self::Prehashed),
+            "raw-prehash" => Ok(Self::RawPrehash),
+            _ => Err(format!("unsupported hashing mode: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for Hashing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A trial's message along with the prehash it is signed over, which is
+/// computed once however many times the trial needs it.
+#[derive(Clone, Debug)]
+pub struct TrialInput<'a> {
+    msg: &'a [u8],
+    hashing: Hashing,
+    prehash: Cow<'a, [u8]>,
+}
+
+impl<'a> TrialInput<'a> {
+    /// Prepare `msg` to be signed and recovered from as `hashing` says.
+    pub fn new(msg: &'a [u8], hashing: Hashing) -> Self {
+        let prehash = match hashing {
+            Hashing::Message | Hashing::Prehashed => Cow::Owned(Sha256::digest(msg).to_vec()),
+            Hashing::RawPrehash => Cow::Borrowed(msg),
+        };
+        Self {
+            msg,
+            hashing,
+            prehash,
+        }
+    }
+
+    /// The message as given.
+    pub fn message(&self) -> &'a [u8] {
+        self.msg
+    }
+
+    /// How the message is passed to the signing and recovery APIs.
+    pub fn hashing(&self) -> Hashing {
+        self.hashing
+    }
+
+    /// The prehash signed over: the message's SHA-256 digest, or with
+    /// [`Hashing::RawPrehash`] the message itself.
+    pub fn prehash(&self) -> &[u8] {
+        &self.prehash
+    }
+}
+
+/// Outcome of recovering a verifying key from a signature.
+#[derive(Debug)]
+pub struct Recovery {
+    /// The key recovered from the signature.
+    pub recovered: VerifyingKey,
+
+    /// Whether the recovered key is the signer's key.
+    pub matched: bool,
+
+    /// Whether the recovered key verifies the signature, matching or not.
+    pub verified: bool,
+
+    /// Time spent recovering the key, not counting verification.
+    pub recover_time: Duration,
+}
+
+impl Recovery {
+    /// Whether the trial passed: the recovered key is the signer's and
+    /// verifies the signature.
+    pub fn passed(&self) -> bool {
+        self.matched && self.verified
+    }
+}
+
+/// Sign `input` with `signing_key`, returning the signature and its recovery
+/// ID.
+pub fn sign(signing_key: &SigningKey, input: &TrialInput<'_>) -> (Signature, RecoveryId) {
+    match input.hashing {
+        Hashing::Message => {
+            let _span = profiling::Span::enter("sign_recoverable");
+            signing_key.sign_recoverable(input.msg)
+        }
+        Hashing::Prehashed | Hashing::RawPrehash => {
+            let _span = profiling::Span::enter("sign_prehash_recoverable");
+            signing_key.sign_prehash_recoverable(input.prehash())
+        }
+    }
+    .expect("signing with a valid key failed")
+}
+
+/// Recover the verifying key from `signature` over `input` with
+/// `recovery_id`, then check that it is `signer` and that it verifies the
+/// signature, passing `input` to the APIs the way [`sign`] does.
+///
+/// Returns an error if no key can be recovered at all.
+pub fn recover(
+    signer: &VerifyingKey,
+    input: &TrialInput<'_>,
+    signature: &Signature,
+    recovery_id: RecoveryId,
+) -> ecdsa::Result<Recovery> {
+    let recover_start = Instant::now();
+    let recovered = match input.hashing {
+        Hashing::Message => {
+            let _span = profiling::Span::enter("recover_from_msg");
+            VerifyingKey::recover_from_msg(input.msg, signature, recovery_id)
+        }
+        Hashing::Prehashed | Hashing::RawPrehash => {
+            let _span = profiling::Span::enter("recover_from_prehash");
+            VerifyingKey::recover_from_prehash(input.prehash(), signature, recovery_id)
+        }
+    }?;
+    let recover_time = recover_start.elapsed();
+
+    let verified = {
+        let _span = profiling::Span::enter("verify");
+        match input.hashing {
+            Hashing::Message => recovered.verify(input.msg, signature),
+            Hashing::Prehashed | Hashing::RawPrehash => {
+                recovered.verify_prehash(input.prehash(), signature)
+            }
+        }
+        .is_ok()
+    };
+
+    Ok(Recovery {
+        matched: &recovered == signer,
+        recovered,
+        verified,
+        recover_time,
+    })
+}
+
+/// Sign `msg` with a P-256 key freshly generated from `rng`, round-trip the
+/// signature through its byte encoding, recover the verifying key from it,
+/// and check that it is the signer's key and verifies the signature.
+///
+/// This is the trial the harness binary runs for each iteration, without
+/// its diagnostics or flag-dependent variations. Returns whether it passed.
+pub fn recovery_trial(rng: &mut impl CryptoRngCore, msg: &[u8]) -> bool {
+    let sk = SigningKey::random(rng);
+    let input = TrialInput::new(msg, Hashing::Message);
+    let (signature, recovery_id) = sign(&sk, &input);
+
+    let signature = Signature::try_from(signature.to_bytes().as_slice())
+        .expect("signature failed to round-trip through its encoding");
+    recover(sk.verifying_key(), &input, &signature, recovery_id)
+        .is_ok_and(|recovery| recovery.passed())
+}
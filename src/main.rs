@@ -1,36 +1,1227 @@
+mod alloc_count;
+mod any_key;
+mod backend;
+mod build_info;
+mod concurrent;
+mod config;
+mod corpus;
+mod diagnostics;
+mod erc2098;
+mod nonce;
+mod openmetrics;
+mod pubkey;
+mod reference;
+mod replay;
+mod rng;
+mod sweep;
+mod tap;
+mod timing;
+
+use any_key::AnyPublicKey;
+use config::{Command, Config, CurveName, Entropy, Format, Mode};
+use diagnostics::{MismatchCounts, MismatchKind, RPointAnalysis};
+use ecdsa::RecoveryId;
 use p256::{
-    ecdsa::{Signature, SigningKey, VerifyingKey},
-    elliptic_curve::rand_core::OsRng,
-    PublicKey, pkcs8::EncodePublicKey,
+    ecdsa::{Signature, SigningKey},
+    elliptic_curve::{rand_core::CryptoRngCore, sec1::ToEncodedPoint},
+    NonZeroScalar, PublicKey,
+};
+use sha2::{Digest, Sha256};
+use signature_test::{profiling, Hashing, TrialInput};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fmt::Write as _,
+    fs::{File, OpenOptions},
+    io::{self, Write as _},
+    process,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
 };
+use tap::Tap;
+
+/// Outcome of running every trial for one (curve, message) pair.
+struct RunSummary<'a> {
+    curve: CurveName,
+    message: &'a [u8],
+    iterations: u64,
+    failed: u64,
+    seed: Option<u64>,
+    entropy: Option<Entropy>,
+    /// How the message was passed to the signing and recovery APIs, with
+    /// `--prehashed` or `--raw-prehash`.
+    hashing: Hashing,
+    corrupt_recovery_id: bool,
+    allocations: Option<u64>,
+    large_recovery_ids: Option<u64>,
+    /// Trials whose outcome [`diagnostics::S_HIGH_HYPOTHESIS`] predicted
+    /// correctly, with `--predict-failure-rate`.
+    correct_predictions: Option<u64>,
+    mismatches: MismatchCounts,
+    /// Trials whose recovered key didn't verify the signature, whether or
+    /// not it matched the signer's key.
+    unverified: u64,
+    /// Trials and failures for each recovery ID returned by signing.
+    recovery_ids: [(u64, u64); 4],
+    /// Disagreements with the reference scalar multiplication, with
+    /// `--reference-mult`.
+    reference: Option<reference::Disagreements>,
+    /// Failures split by nonce properties, with `--analyze-nonce`.
+    nonce: Option<nonce::Correlations>,
+    /// Throughput and per-phase timings, with `--timing`.
+    timing: Option<timing::Timing>,
+    /// Trials whose freshly generated key had already been generated, not
+    /// counted for the fixed entropy source.
+    duplicate_keys: u64,
+    first_failure: Option<Failure>,
+}
+
+/// Outcome of a single sign+recover trial.
+struct Trial {
+    /// The signer's freshly generated public key.
+    public_key: AnyPublicKey,
+
+    /// Whether the recovered key matched the signer's key and verifies the
+    /// signature.
+    matched: bool,
+
+    /// Whether the recovered key, matching or not, fails to verify the
+    /// signature.
+    unverified: bool,
+
+    /// Recovery ID returned by signing, before any transformation.
+    recovery_id: u8,
+
+    /// Whether [`diagnostics::S_HIGH_HYPOTHESIS`] predicts a mismatch.
+    predicted_failure: bool,
+
+    /// How the recovered key relates to the signer's, if they didn't match.
+    mismatch: Option<MismatchKind>,
+
+    /// Whether the library's and the recovered key differ from the
+    /// reference scalar multiplication, with [`Config::reference_mult`].
+    reference: Option<(bool, bool)>,
+
+    /// Properties of the signing nonce, with [`Config::analyze_nonce`].
+    nonce: Option<nonce::Features>,
+
+    /// Time spent signing.
+    sign_time: Duration,
+
+    /// Time spent recovering, or zero if the trial failed before recovery.
+    recover_time: Duration,
+
+    /// Details of the failure, if the keys didn't match.
+    failure: Option<Failure>,
+}
+
+/// Identifies a failing trial.
+struct Failure {
+    /// Recovery ID the failing recovery was attempted with.
+    recovery_id: u8,
+
+    /// Truncated SHA-256 of the trial's secret key, message, signature, and
+    /// recovery ID.
+    trace_id: [u8; 16],
+
+    /// The trial's inputs and keys in hex, with [`Config::fail_fast`].
+    dump: Option<String>,
+
+    /// The trial's line in the [`Config::dump_failures`] file.
+    jsonl: Option<String>,
+}
+
+impl RunSummary<'_> {
+    /// Number of trials with the wrong outcome: mismatches, or with
+    /// [`Config::corrupt_recovery_id`], matches.
+    fn unexpected(&self) -> u64 {
+        if self.corrupt_recovery_id {
+            self.iterations - self.failed
+        } else {
+            self.failed
+        }
+    }
+}
+
+impl Failure {
+    fn new(sk: &SigningKey, msg: &[u8], signature: &[u8], recovery_id: u8) -> Self {
+        let digest = Sha256::new()
+            .chain_update(sk.to_bytes())
+            .chain_update(msg)
+            .chain_update(signature)
+            .chain_update([recovery_id])
+            .finalize();
+
+        let mut trace_id = [0; 16];
+        trace_id.copy_from_slice(&digest[..16]);
+        Self {
+            recovery_id,
+            trace_id,
+            dump: None,
+            jsonl: None,
+        }
+    }
+}
 
 fn main() {
+    let config = match Command::from_env() {
+        Ok(Command::Run(config)) => config,
+        Ok(Command::Help) => {
+            print!("{}", config::USAGE);
+            return;
+        }
+        Ok(Command::Version(format)) => {
+            match format {
+                Format::Text | Format::OpenMetrics | Format::Tap => {
+                    print!("{}", build_info::text())
+                }
+                Format::Json => println!("{}", build_info::json()),
+            }
+            return;
+        }
+        Ok(Command::Pubkey(options)) => {
+            match pubkey::run(&options) {
+                Ok(output) => {
+                    if let Err(e) = io::stdout().write_all(&output) {
+                        eprintln!("error: {e}");
+                        process::exit(1);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("error: {e}");
+                    process::exit(1);
+                }
+            }
+            return;
+        }
+        Ok(Command::Replay(path)) => match replay::run(&path) {
+            Ok(replays) => {
+                let yes_no = |b: bool| if b { "yes" } else { "no" };
+                let mut still_failing = 0;
+                for (line, replay) in &replays {
+                    if !replay.passed() {
+                        still_failing += 1;
+                    }
+                    println!("line {line}:");
+                    println!(
+                        "  Signing reproduces the captured signature: {}",
+                        yes_no(replay.signature_reproduced)
+                    );
+                    match replay.mismatch {
+                        None => println!("  Recovered key matches the signer's key"),
+                        Some(kind) => {
+                            println!("  Recovered key does not match the signer's key ({kind})")
+                        }
+                    }
+                    println!(
+                        "  Recovered key verifies the signature: {}",
+                        yes_no(replay.verified)
+                    );
+                }
+                println!(
+                    "Replayed {} reproducers: {} fixed, {} still failing",
+                    replays.len(),
+                    replays.len() - still_failing,
+                    still_failing
+                );
+                if still_failing > 0 {
+                    process::exit(1);
+                }
+                return;
+            }
+            Err(e) => {
+                eprintln!("error: {e}");
+                process::exit(2);
+            }
+        },
+        Err(e) => {
+            eprintln!("error: {e}\n\n{}", config::USAGE);
+            process::exit(2);
+        }
+    };
+
+    if config.flamegraph.is_some() && !cfg!(feature = "profiling") {
+        eprintln!("error: --flamegraph requires building with `--features profiling`");
+        process::exit(2);
+    }
+
+    if config.mode == Mode::CompareBackends {
+        if compare_backends(&config) > 0 {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if config.mode == Mode::OutputKeys {
+        output_keys(&config);
+        return;
+    }
+
+    if config.mode == Mode::Concurrent {
+        let anomalies = concurrent::run(&config);
+        write_profile(&config);
+        if anomalies > 0 {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if config.mode == Mode::LengthSweep {
+        sweep::run(&config);
+        write_profile(&config);
+        return;
+    }
+
+    let sources: Vec<_> = if config.entropy.is_empty() {
+        vec![None]
+    } else {
+        config.entropy.iter().copied().map(Some).collect()
+    };
+    let mut modes = vec![Hashing::Message];
+    if config.prehashed {
+        modes.push(Hashing::Prehashed);
+    }
+    if config.raw_prehash {
+        modes.push(Hashing::RawPrehash);
+    }
+    let multiple_runs =
+        config.curves.len() * config.messages.len() * sources.len() * modes.len() > 1;
+    let mut summaries = Vec::new();
+    let mut tap = (config.format == Format::Tap).then(Tap::default);
+    let mut failures = config.dump_failures.as_ref().map(|path| {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap_or_else(|e| {
+                eprintln!("error: {}: {e}", path.display());
+                process::exit(1);
+            })
+    });
+    let mut unexpected = false;
+    let mut stopped = false;
+
+    'runs: for &curve in &config.curves {
+        for message in &config.messages {
+            for &hashing in &modes {
+                if stopped {
+                    break 'runs;
+                }
+                let runs: Vec<_> = sources
+                    .iter()
+                    .map_while(|&entropy| {
+                        if stopped {
+                            return None;
+                        }
+                        let summary = run(
+                            &config,
+                            curve,
+                            message,
+                            entropy,
+                            hashing,
+                            tap.as_mut(),
+                            failures.as_mut(),
+                        );
+                        stopped = config.fail_fast && summary.failed > 0;
+                        Some(summary)
+                    })
+                    .collect();
+                unexpected |= runs.iter().any(|summary| summary.unexpected() > 0);
+
+                match config.format {
+                    Format::OpenMetrics => {
+                        summaries.extend(runs);
+                        continue;
+                    }
+                    Format::Tap => continue,
+                    Format::Text | Format::Json => (),
+                }
+                for summary in &runs {
+                    print_summary(summary, config.format, multiple_runs);
+                }
+                if config.format == Format::Text && runs.len() > 1 {
+                    print_entropy_comparison(&runs);
+                }
+            }
+        }
+    }
+
+    if config.format == Format::OpenMetrics {
+        print!("{}", openmetrics::render(&summaries));
+    }
+    if stopped {
+        eprintln!("--fail-fast: stopped after the first failing trial");
+    }
+    write_profile(&config);
+    if let Some(tap) = tap {
+        tap.plan();
+        if tap.failed() > 0 {
+            process::exit(1);
+        }
+    }
+    if unexpected {
+        process::exit(1);
+    }
+}
+
+fn run<'a>(
+    config: &Config,
+    curve: CurveName,
+    msg: &'a [u8],
+    entropy: Option<Entropy>,
+    hashing: Hashing,
+    mut tap: Option<&mut Tap>,
+    mut failures: Option<&mut File>,
+) -> RunSummary<'a> {
+    let seed = rng::effective_seed(entropy, config.seed);
     let mut failed = 0;
-    let n = 10;
-    for _ in 0..n {
-        let sk = SigningKey::random(&mut OsRng);
-        let msg = b"hello";
-        let (signature, v) = sk.sign_recoverable(&msg[..]).unwrap();
-
-        let signature_bytes = signature.to_bytes();
-        let recovery_id = v.to_byte();
-
-        let signature = Signature::try_from(signature_bytes.as_slice()).unwrap();
-        let recovered_vk =
-            VerifyingKey::recover_from_msg(&msg[..], &signature, recovery_id.try_into().unwrap())
-                .unwrap();
-
-        let pk: PublicKey = recovered_vk.into();
-        let vpk = sk.verifying_key().into();
-        if pk != vpk {
-            dbg!(pk, vpk);
+    let mut large_recovery_ids = 0;
+    let mut correct_predictions = 0;
+    let mut mismatches = MismatchCounts::default();
+    let mut unverified = 0;
+    let mut recovery_ids = [(0, 0); 4];
+    let mut nonce = nonce::Correlations::default();
+    let mut reference = reference::Disagreements::default();
+    let mut timing = timing::Timing::default();
+    let mut keys = HashMap::new();
+    let mut duplicate_keys = 0;
+    let mut first_failure = None;
+    alloc_count::take();
+
+    // `iteration` numbers the trial within the run, and `index` within the
+    // trials drawn from an RNG seeded with `seed`. Returns whether the run
+    // should stop, with `--fail-fast`.
+    let mut record = |iteration: u64, seed: Option<u64>, index: u64, trial: Trial| {
+        if let Some(tap) = &mut tap {
+            let description = format!(
+                "{} iteration {iteration}",
+                run_label(curve, msg, entropy, hashing)
+            );
+            report_tap(tap, config, &description, &trial);
+        }
+        if !trial.matched {
             failed += 1;
+            // Only the trial which stops the run has a dump, so it's printed
+            // once even if other threads' trials failed in the meantime.
+            if let Some(dump) = trial.failure.as_ref().and_then(|f| f.dump.as_ref()) {
+                eprint!("{dump}");
+            }
+            let line = trial.failure.as_ref().and_then(|f| f.jsonl.as_ref());
+            if let (Some(file), Some(line)) = (&mut failures, line) {
+                if let Err(e) = writeln!(file, "{line}") {
+                    let path = config.dump_failures.as_ref().expect("no failure file");
+                    eprintln!("warning: failed to write to {}: {e}", path.display());
+                }
+            }
+            first_failure = first_failure.take().or(trial.failure);
+            if let Some(seed) = seed.filter(|_| !config.corrupt_recovery_id) {
+                // Each trial draws only its key from the RNG, so the first
+                // `index + 1` trials of a rerun regenerate this one's.
+                let entropy = entropy.map_or_else(String::new, |e| format!(" --entropy {e}"));
+                eprintln!(
+                    "failing trial: seed {seed}, iteration {index} (rerun with --seed {seed}{entropy} --iterations {} to regenerate its key)",
+                    index + 1
+                );
+            }
+        }
+        if let Some(kind) = trial.mismatch {
+            mismatches.record(kind);
+        }
+        unverified += u64::from(trial.unverified);
+        timing.trials += 1;
+        timing.sign += trial.sign_time;
+        timing.recover += trial.recover_time;
+        if let Some((library, recovered)) = trial.reference {
+            reference.library += u64::from(library);
+            reference.recovered += u64::from(recovered);
+        }
+        if let Some(features) = trial.nonce {
+            nonce.record(features, !trial.matched);
+        }
+        // The fixed source repeats one key by design, so every trial after
+        // the first would be reported.
+        let key = (entropy != Some(Entropy::Fixed)).then_some(trial.public_key);
+        match key.map(|key| keys.entry(key)) {
+            Some(Entry::Occupied(first)) => {
+                duplicate_keys += 1;
+                eprintln!(
+                    "warning: iteration {iteration} generated the same {} key as iteration {}: {}",
+                    trial.public_key.curve(),
+                    first.get(),
+                    hex(&trial.public_key.to_sec1_bytes(true))
+                );
+            }
+            Some(Entry::Vacant(slot)) => {
+                slot.insert(iteration);
+            }
+            None => (),
+        }
+        if trial.recovery_id > 1 {
+            large_recovery_ids += 1;
+        }
+        let (trials, failures) = &mut recovery_ids[usize::from(trial.recovery_id)];
+        *trials += 1;
+        *failures += u64::from(!trial.matched);
+        if trial.predicted_failure != trial.matched {
+            correct_predictions += 1;
         }
+        config.fail_fast && !trial.matched
+    };
+
+    let start = Instant::now();
+    if config.threads == 1 {
+        let mut rng = rng::for_run(entropy, seed);
+        for iteration in 0..config.iterations {
+            let trial = match curve {
+                CurveName::P256 => p256_trial(&mut *rng, msg, hashing, config),
+            };
+            if record(iteration, seed, iteration, trial) {
+                break;
+            }
+        }
+    } else {
+        // Thread `t` runs the contiguous block of iterations starting at
+        // `start(t)`, seeded with the run's seed plus `t`.
+        let threads = config.threads as u64;
+        let start = |thread: u64| {
+            (u128::from(config.iterations) * u128::from(thread) / u128::from(threads)) as u64
+        };
+        thread::scope(|scope| {
+            let (sender, receiver) = mpsc::sync_channel(1024);
+            for thread in 0..threads {
+                let sender = sender.clone();
+                let (first, end) = (start(thread), start(thread + 1));
+                scope.spawn(move || {
+                    let seed = seed.map(|seed| seed.wrapping_add(thread));
+                    let mut rng = rng::for_run(entropy, seed);
+                    for iteration in first..end {
+                        let trial = match curve {
+                            CurveName::P256 => p256_trial(&mut *rng, msg, hashing, config),
+                        };
+                        if sender
+                            .send((iteration, seed, iteration - first, trial))
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                });
+            }
+            drop(sender);
+            // Stopping drops the receiver, so the other threads' next send
+            // fails and they stop too.
+            for (iteration, seed, index, trial) in receiver {
+                if record(iteration, seed, index, trial) {
+                    break;
+                }
+            }
+        });
     }
-    println!(
-        "Failure rate: {}/{} = {:.02}%",
+
+    timing.wall = start.elapsed();
+
+    RunSummary {
+        curve,
+        message: msg,
+        // Fewer than `config.iterations` if `--fail-fast` stopped the run.
+        iterations: timing.trials,
         failed,
-        n,
-        failed as f64 / n as f64 * 100.0
+        seed: config.seed,
+        entropy,
+        hashing,
+        corrupt_recovery_id: config.corrupt_recovery_id,
+        // Other threads' allocations would be counted too.
+        allocations: alloc_count::take().filter(|_| config.threads == 1),
+        large_recovery_ids: config.check_recovery_id.then_some(large_recovery_ids),
+        correct_predictions: config.predict_failure_rate.then_some(correct_predictions),
+        mismatches,
+        unverified,
+        recovery_ids,
+        reference: config.reference_mult.then_some(reference),
+        nonce: config.analyze_nonce.then_some(nonce),
+        timing: config.timing.then_some(timing),
+        duplicate_keys,
+        first_failure,
+    }
+}
+
+/// Write the profile recorded so far to the `--flamegraph` file, if any.
+fn write_profile(config: &Config) {
+    if let Some(path) = &config.flamegraph {
+        if let Err(e) = profiling::write_folded(path) {
+            eprintln!("error: {}: {e}", path.display());
+            process::exit(1);
+        }
+    }
+}
+
+/// Emit `trial` as a TAP test point. A trial passes if the signer's key was
+/// recovered, or with [`Config::corrupt_recovery_id`], if it wasn't.
+fn report_tap(tap: &mut Tap, config: &Config, description: &str, trial: &Trial) {
+    let ok = trial.matched != config.corrupt_recovery_id;
+    let mut diagnostics = Vec::new();
+    if !ok {
+        if trial.matched {
+            diagnostics.push("corrupted recovery ID still recovered the signer's key".to_owned());
+        }
+        if let Some(kind) = trial.mismatch {
+            diagnostics.push(format!("recovered key: {kind}"));
+        }
+        if let Some(failure) = &trial.failure {
+            diagnostics.push(format!("recovery_id: {}", failure.recovery_id));
+            diagnostics.push(format!("trace_id: {}", hex(&failure.trace_id)));
+        }
+    }
+
+    tap.result(ok, description, &diagnostics);
+}
+
+/// Human-readable name of the run of `msg` on `curve`, e.g.
+/// `p256 "hello" (os entropy, prehashed)`.
+fn run_label(curve: CurveName, msg: &[u8], entropy: Option<Entropy>, hashing: Hashing) -> String {
+    let mut qualifiers = Vec::new();
+    if let Some(entropy) = entropy {
+        qualifiers.push(format!("{entropy} entropy"));
+    }
+    match hashing {
+        Hashing::Message => (),
+        Hashing::Prehashed => qualifiers.push("prehashed".to_owned()),
+        Hashing::RawPrehash => qualifiers.push("raw prehash".to_owned()),
+    }
+
+    let mut label = format!("{curve} {:?}", String::from_utf8_lossy(msg));
+    if !qualifiers.is_empty() {
+        let _ = write!(label, " ({})", qualifiers.join(", "));
+    }
+    label
+}
+
+/// Sign `msg` with a fresh key, recover the verifying key from the signature,
+/// and check it matches the signer's key. `hashing` picks whether `msg` is
+/// passed to the message APIs, hashed once and passed to the prehash APIs, or
+/// passed to the prehash APIs as is.
+///
+/// With [`Config::erc2098`], the signature is packed into and unpacked from
+/// the ERC-2098 compact encoding first. With [`Config::corrupt_recovery_id`],
+/// the recovery ID's parity bit is flipped before recovering, so a mismatch is
+/// the expected outcome and isn't reported on stderr.
+fn p256_trial(
+    mut rng: &mut dyn CryptoRngCore,
+    msg: &[u8],
+    hashing: Hashing,
+    config: &Config,
+) -> Trial {
+    let _trial_span = profiling::Span::enter("p256_trial");
+    let sk = {
+        let _span = profiling::Span::enter("SigningKey::random");
+        SigningKey::random(&mut rng)
+    };
+    let allocations = alloc_count::Span::start();
+    let sign_start = Instant::now();
+    let input = TrialInput::new(msg, hashing);
+    let (signature, mut v) = signature_test::sign(&sk, &input);
+    let sign_time = sign_start.elapsed();
+    let prehash = input.prehash();
+    let mut trial = Trial {
+        public_key: PublicKey::from(sk.verifying_key()).into(),
+        matched: false,
+        unverified: false,
+        recovery_id: v.to_byte(),
+        predicted_failure: diagnostics::s_high_predicts_failure(
+            &signature,
+            config.corrupt_recovery_id,
+        ),
+        mismatch: None,
+        reference: None,
+        nonce: config
+            .analyze_nonce
+            .then(|| nonce::Features::new(&sk, prehash, &signature)),
+        sign_time,
+        recover_time: Duration::ZERO,
+        failure: None,
+    };
+
+    let reference_key = config
+        .reference_mult
+        .then(|| reference::mul_base(&sk.to_bytes().into()));
+    if let Some(reference_key) = &reference_key {
+        let library_key = PublicKey::from_secret_scalar(sk.as_nonzero_scalar());
+        let library_key = library_key.to_encoded_point(false);
+        let disagrees = reference_key.as_ref().map(|key| &key[..]) != Some(library_key.as_bytes());
+        if disagrees {
+            eprintln!(
+                "reference scalar multiplication disagrees with from_secret_scalar: secret={} library={} reference={}",
+                hex(&sk.to_bytes()),
+                hex(library_key.as_bytes()),
+                reference_key.as_ref().map_or_else(|| "identity".to_owned(), |key| hex(key))
+            );
+        }
+        trial.reference = Some((disagrees, false));
+    }
+
+    if config.check_recovery_id && v.is_x_reduced() {
+        eprintln!(
+            "recovery id {} (x-reduced) from secret={} message={:?} signature={}",
+            v.to_byte(),
+            hex(&sk.to_bytes()),
+            String::from_utf8_lossy(msg),
+            hex(&signature.to_bytes())
+        );
+    }
+
+    let signature_bytes = if config.erc2098 {
+        let erc2098_failure = |signature: &[u8], recovery_id: u8| {
+            let mut failure = Failure::new(&sk, msg, signature, recovery_id);
+            failure.dump = config.fail_fast.then(|| {
+                let signer = sk.verifying_key().into();
+                failure_dump(msg, signature, recovery_id, &signer, None)
+            });
+            failure
+        };
+        let compact = match erc2098::to_erc2098(&signature, v) {
+            Ok(compact) => compact,
+            Err(_) => {
+                eprintln!(
+                    "ERC-2098 can't represent recovery id {} (x-reduced)",
+                    v.to_byte()
+                );
+                trial.failure = Some(erc2098_failure(&signature.to_bytes(), v.to_byte()));
+                return trial;
+            }
+        };
+        let (unpacked, unpacked_v) = match erc2098::from_erc2098(&compact) {
+            Ok(unpacked) => unpacked,
+            Err(_) => {
+                eprintln!(
+                    "ERC-2098 compact signature failed to unpack: {}",
+                    hex(&compact)
+                );
+                trial.failure = Some(erc2098_failure(&compact, v.to_byte()));
+                return trial;
+            }
+        };
+        let expected_v = v.is_y_odd() ^ signature.normalize_s().is_some();
+        if unpacked_v.is_y_odd() != expected_v {
+            eprintln!(
+                "ERC-2098 round trip changed the recovery id: {} -> {}",
+                v.to_byte(),
+                unpacked_v.to_byte()
+            );
+            trial.failure = Some(erc2098_failure(&compact, unpacked_v.to_byte()));
+            return trial;
+        }
+        v = unpacked_v;
+        unpacked.to_bytes()
+    } else {
+        signature.to_bytes()
+    };
+    if config.corrupt_recovery_id {
+        v = RecoveryId::new(!v.is_y_odd(), v.is_x_reduced());
+    }
+    let recovery_id = v.to_byte();
+
+    let signature = Signature::try_from(signature_bytes.as_slice()).unwrap();
+    let recovery = signature_test::recover(sk.verifying_key(), &input, &signature, v).unwrap();
+    allocations.finish();
+    trial.recover_time = recovery.recover_time;
+    trial.unverified = !recovery.verified;
+
+    let recovered_vk = recovery.recovered;
+    let pk: PublicKey = recovered_vk.into();
+    let vpk: PublicKey = sk.verifying_key().into();
+    let reproducer = || replay::Reproducer {
+        curve: CurveName::P256,
+        secret: sk.to_bytes().to_vec(),
+        message: msg.to_vec(),
+        hashing,
+        signature: signature_bytes.to_vec(),
+        recovery_id,
+    };
+    // Failures are expected with `--corrupt-recovery-id`, so aren't dumped.
+    let jsonl = || {
+        (config.dump_failures.is_some() && !config.corrupt_recovery_id).then(|| {
+            reproducer().to_json_with_keys(
+                vpk.to_encoded_point(false).as_bytes(),
+                pk.to_encoded_point(false).as_bytes(),
+            )
+        })
+    };
+    if let (Some((_, recovered)), Some(reference_key)) = (&mut trial.reference, &reference_key) {
+        *recovered = reference_key.as_ref().map(|key| &key[..])
+            != Some(pk.to_encoded_point(false).as_bytes());
+    }
+    if !recovery.matched {
+        let kind =
+            MismatchKind::classify(sk.verifying_key(), &recovered_vk, prehash, &signature, v);
+        if !config.corrupt_recovery_id {
+            eprintln!("{}", diagnostics::describe_difference(&vpk, &pk));
+            eprintln!(
+                "R-point analysis: {}",
+                RPointAnalysis::new(sk.verifying_key(), prehash, &signature, v)
+            );
+            eprintln!("recovered key: {kind}");
+            eprintln!("reproducer: {}", reproducer().to_json());
+            // The fuzz target hashes its message with SHA-256, so only raw
+            // prehashes don't fit.
+            if let Some(dir) = config
+                .save_corpus
+                .as_ref()
+                .filter(|_| hashing != Hashing::RawPrehash)
+            {
+                if let Err(e) = corpus::save(dir, &sk.to_bytes().into(), msg) {
+                    eprintln!(
+                        "warning: failed to save fuzz input to {}: {e}",
+                        dir.display()
+                    );
+                }
+            }
+        }
+        trial.mismatch = Some(kind);
+        let mut failure = Failure::new(&sk, msg, &signature_bytes, recovery_id);
+        failure.dump = config
+            .fail_fast
+            .then(|| failure_dump(msg, &signature_bytes, recovery_id, &vpk, Some(&pk)));
+        failure.jsonl = jsonl();
+        trial.failure = Some(failure);
+        return trial;
+    }
+
+    if trial.unverified {
+        if !config.corrupt_recovery_id {
+            eprintln!(
+                "recovered key matches the signer's but doesn't verify the signature: secret {}, message {:?}, signature {}",
+                hex(&sk.to_bytes()),
+                String::from_utf8_lossy(msg),
+                hex(&signature_bytes)
+            );
+        }
+        let mut failure = Failure::new(&sk, msg, &signature_bytes, recovery_id);
+        failure.dump = config
+            .fail_fast
+            .then(|| failure_dump(msg, &signature_bytes, recovery_id, &vpk, Some(&pk)));
+        failure.jsonl = jsonl();
+        trial.failure = Some(failure);
+        return trial;
+    }
+
+    trial.matched = true;
+    trial
+}
+
+/// Describe everything needed to reproduce a failing trial by hand, for
+/// `--fail-fast`. Keys are uncompressed SEC1; `recovered` is `None` if the
+/// trial failed before recovering.
+fn failure_dump(
+    msg: &[u8],
+    signature: &[u8],
+    recovery_id: u8,
+    signer: &PublicKey,
+    recovered: Option<&PublicKey>,
+) -> String {
+    let key = |key: &PublicKey| hex(key.to_encoded_point(false).as_bytes());
+    format!(
+        "first failing trial:\n  message:       {}\n  signature:     {}\n  recovery id:   {}\n  signer's key:  {}\n  recovered key: {}\n",
+        hex(msg),
+        hex(signature),
+        recovery_id,
+        key(signer),
+        recovered.map_or_else(|| "none".to_owned(), key)
+    )
+}
+
+/// Run identical inputs through every backend and report any output which
+/// differs from the baseline. Returns the number of divergent trials.
+fn compare_backends(config: &Config) -> u64 {
+    let (baseline, others) = backend::BACKENDS
+        .split_first()
+        .expect("no backends configured");
+
+    // A backend compared with itself can't diverge, so a result would only
+    // look like a passing check.
+    if others.is_empty() {
+        eprintln!(
+            "error: only one backend ({}) built, nothing to compare",
+            baseline.name()
+        );
+        process::exit(2);
+    }
+
+    let mut rng = rng::for_seed(config.seed);
+    let mut trials = 0;
+    let mut divergent = 0;
+
+    for msg in &config.messages {
+        for _ in 0..config.iterations {
+            let (secret, expected) = loop {
+                let mut secret = [0u8; 32];
+                rng.fill_bytes(&mut secret);
+
+                if let Some(observations) = baseline.observe(&secret, msg) {
+                    break (secret, observations);
+                }
+            };
+
+            trials += 1;
+            let mut diverged = false;
+
+            for backend in others {
+                let fields = match backend.observe(&secret, msg) {
+                    Some(observations) => expected.diff(&observations),
+                    None => vec!["secret key rejected"],
+                };
+
+                if !fields.is_empty() {
+                    diverged = true;
+                    println!(
+                        "divergence: {} vs {}: secret={} message={:?}: {}",
+                        baseline.name(),
+                        backend.name(),
+                        hex(&secret),
+                        String::from_utf8_lossy(msg),
+                        fields.join(", ")
+                    );
+                }
+            }
+
+            if diverged {
+                divergent += 1;
+            }
+        }
+    }
+
+    println!(
+        "Backend divergences: {}/{} trials across {} backends",
+        divergent,
+        trials,
+        backend::BACKENDS.len()
     );
+    divergent
+}
+
+/// Print `iterations` freshly generated public keys for each curve, as
+/// compressed SEC1 hex.
+fn output_keys(config: &Config) {
+    let mut rng = rng::for_seed(config.seed);
+
+    for &curve in &config.curves {
+        for _ in 0..config.iterations {
+            let public_key = match curve {
+                CurveName::P256 => {
+                    let secret = NonZeroScalar::random(&mut rng);
+                    PublicKey::from_secret_scalar(&secret)
+                        .to_encoded_point(true)
+                        .as_bytes()
+                        .to_vec()
+                }
+            };
+
+            match config.format {
+                Format::Text | Format::OpenMetrics | Format::Tap => {
+                    println!("{}", hex(&public_key))
+                }
+                Format::Json => println!(
+                    "{{\"curve\":\"{}\",\"public_key\":\"{}\"}}",
+                    curve,
+                    hex(&public_key)
+                ),
+            }
+        }
+    }
+}
+
+fn print_summary(summary: &RunSummary<'_>, format: Format, labelled: bool) {
+    let rate = if summary.iterations == 0 {
+        0.0
+    } else {
+        summary.failed as f64 / summary.iterations as f64
+    };
+
+    match format {
+        Format::Text | Format::OpenMetrics | Format::Tap => {
+            if labelled {
+                println!(
+                    "{}:",
+                    run_label(
+                        summary.curve,
+                        summary.message,
+                        summary.entropy,
+                        summary.hashing
+                    )
+                );
+            }
+            if summary.corrupt_recovery_id {
+                let matched = summary.iterations - summary.failed;
+                println!(
+                    "Corrupted recovery ID still recovered the signer's key: {}/{} = {:.02}%",
+                    matched,
+                    summary.iterations,
+                    (1.0 - rate) * 100.0
+                );
+            } else {
+                println!(
+                    "Failure rate: {}/{} = {:.02}%",
+                    summary.failed,
+                    summary.iterations,
+                    rate * 100.0
+                );
+            }
+            if let Some(correct) = summary.correct_predictions {
+                let predicted = predicted_failure_rate(summary);
+                println!(
+                    "Predicted failure rate ({}): {:.02}%",
+                    diagnostics::S_HIGH_HYPOTHESIS,
+                    predicted * 100.0
+                );
+                println!(
+                    "Observed failure rate: {:.02}% ({} the prediction within 3 standard deviations)",
+                    rate * 100.0,
+                    if diagnostics::rate_consistent(summary.failed, summary.iterations, predicted) {
+                        "matches"
+                    } else {
+                        "does not match"
+                    }
+                );
+                println!(
+                    "Trials whose outcome was predicted correctly: {}/{}",
+                    correct, summary.iterations
+                );
+            }
+            if summary.mismatches.total() > 0 {
+                println!(
+                    "Mismatched keys: {} from the other R candidate, {} negated, {} unrelated",
+                    summary.mismatches.other_candidate,
+                    summary.mismatches.negation,
+                    summary.mismatches.unrelated
+                );
+            }
+            if summary.failed > 0 {
+                println!(
+                    "Recovered keys that don't verify the signature: {}/{}",
+                    summary.unverified, summary.iterations
+                );
+                let breakdown: Vec<_> = summary
+                    .recovery_ids
+                    .iter()
+                    .enumerate()
+                    .map(|(id, (trials, failures))| format!("{id}: {failures}/{trials}"))
+                    .collect();
+                println!("Failures by recovery ID: {}", breakdown.join(", "));
+            }
+            if let Some(reference) = &summary.reference {
+                println!(
+                    "Disagreements with the reference scalar multiplication: from_secret_scalar {}/{}, recovered key {}/{}",
+                    reference.library,
+                    summary.iterations,
+                    reference.recovered,
+                    summary.iterations
+                );
+            }
+            if let Some(correlations) = &summary.nonce {
+                println!("Failure rate by nonce property:");
+                for ((_, description), table) in nonce::FEATURES.iter().zip(&correlations.tables) {
+                    println!("  {description:<10} {table}");
+                }
+            }
+            if let Some(timing) = &summary.timing {
+                println!(
+                    "Throughput: {:.0} trials/s, {:.1} µs per trial (signing {:.1} µs, recovery {:.1} µs)",
+                    timing.trials_per_second(),
+                    timing.micros_per_trial(),
+                    timing.sign_micros(),
+                    timing.recover_micros()
+                );
+            }
+            if summary.duplicate_keys > 0 {
+                println!(
+                    "Duplicate keys: {} (the RNG repeated itself; results may be invalid)",
+                    summary.duplicate_keys
+                );
+            }
+            if let Some(large) = summary.large_recovery_ids {
+                println!("Recovery IDs with the x-reduced bit set: {large}");
+            }
+            if let Some(allocations) = summary.allocations {
+                println!(
+                    "Allocations per sign+recover: {:.1} ({} total)",
+                    allocations as f64 / summary.iterations.max(1) as f64,
+                    allocations
+                );
+            }
+        }
+        Format::Json => {
+            let seed = summary
+                .seed
+                .map_or_else(|| "null".to_owned(), |seed| seed.to_string());
+            let entropy = summary
+                .entropy
+                .map_or_else(|| "null".to_owned(), |e| json_string(e.as_str()));
+            let allocations = summary
+                .allocations
+                .map_or_else(|| "null".to_owned(), |n| n.to_string());
+            let large_recovery_ids = summary
+                .large_recovery_ids
+                .map_or_else(|| "null".to_owned(), |n| n.to_string());
+            let prediction = summary.correct_predictions.map_or_else(
+                || "null".to_owned(),
+                |correct| {
+                    let predicted = predicted_failure_rate(summary);
+                    format!(
+                        "{{\"hypothesis\":{},\"failure_rate\":{},\"consistent\":{},\"correct_predictions\":{}}}",
+                        json_string(diagnostics::S_HIGH_HYPOTHESIS),
+                        predicted,
+                        diagnostics::rate_consistent(summary.failed, summary.iterations, predicted),
+                        correct
+                    )
+                },
+            );
+            let reference = summary.reference.map_or_else(
+                || "null".to_owned(),
+                |reference| {
+                    format!(
+                        "{{\"library_mismatches\":{},\"recovered_mismatches\":{}}}",
+                        reference.library, reference.recovered
+                    )
+                },
+            );
+            let recovery_ids: Vec<_> = summary
+                .recovery_ids
+                .iter()
+                .map(|(trials, failures)| {
+                    format!("{{\"trials\":{trials},\"failures\":{failures}}}")
+                })
+                .collect();
+            let timing = summary.timing.map_or_else(
+                || "null".to_owned(),
+                |timing| {
+                    format!(
+                        "{{\"seconds\":{},\"trials_per_second\":{},\"micros_per_trial\":{},\"sign_micros\":{},\"recover_micros\":{}}}",
+                        timing.wall.as_secs_f64(),
+                        timing.trials_per_second(),
+                        timing.micros_per_trial(),
+                        timing.sign_micros(),
+                        timing.recover_micros()
+                    )
+                },
+            );
+            let nonce = summary.nonce.map_or_else(
+                || "null".to_owned(),
+                |correlations| {
+                    let tables: Vec<_> = nonce::FEATURES
+                        .iter()
+                        .zip(&correlations.tables)
+                        .map(|((key, _), table)| {
+                            format!(
+                                "{}:{{\"with\":{},\"with_failures\":{},\"without\":{},\"without_failures\":{},\"chi_squared\":{},\"significant\":{}}}",
+                                json_string(key),
+                                table.with,
+                                table.with_failures,
+                                table.without,
+                                table.without_failures,
+                                table
+                                    .chi_squared()
+                                    .map_or_else(|| "null".to_owned(), |chi2| chi2.to_string()),
+                                table.significant()
+                            )
+                        })
+                        .collect();
+                    format!("{{{}}}", tables.join(","))
+                },
+            );
+            println!(
+                "{{\"curve\":\"{}\",\"message\":{},\"iterations\":{},\"failures\":{},\"failure_rate\":{},\"seed\":{},\"entropy\":{},\"prehashed\":{},\"raw_prehash\":{},\"corrupt_recovery_id\":{},\"allocations\":{},\"large_recovery_ids\":{},\"prediction\":{},\"mismatches\":{{\"other_candidate\":{},\"negation\":{},\"unrelated\":{}}},\"unverified\":{},\"recovery_ids\":[{}],\"reference\":{},\"nonce\":{},\"timing\":{},\"duplicate_keys\":{}}}",
+                summary.curve,
+                json_string(&String::from_utf8_lossy(summary.message)),
+                summary.iterations,
+                summary.failed,
+                rate,
+                seed,
+                entropy,
+                summary.hashing == Hashing::Prehashed,
+                summary.hashing == Hashing::RawPrehash,
+                summary.corrupt_recovery_id,
+                allocations,
+                large_recovery_ids,
+                prediction,
+                summary.mismatches.other_candidate,
+                summary.mismatches.negation,
+                summary.mismatches.unrelated,
+                summary.unverified,
+                recovery_ids.join(","),
+                reference,
+                nonce,
+                timing,
+                summary.duplicate_keys
+            );
+        }
+    }
+}
+
+/// Failure rate [`diagnostics::S_HIGH_HYPOTHESIS`] predicts for `summary`'s
+/// configuration.
+fn predicted_failure_rate(summary: &RunSummary<'_>) -> f64 {
+    if summary.corrupt_recovery_id {
+        1.0 - diagnostics::S_HIGH_FAILURE_RATE
+    } else {
+        diagnostics::S_HIGH_FAILURE_RATE
+    }
+}
+
+/// Print the failure rates of runs which differ only in their entropy source
+/// as a single table, so a source-dependent rate stands out.
+fn print_entropy_comparison(runs: &[RunSummary<'_>]) {
+    println!("Failure rate by entropy source:");
+    for summary in runs {
+        let rate = summary.failed as f64 / summary.iterations.max(1) as f64;
+        println!(
+            "  {:<8} {:>8}/{:<8} {:>7.02}%",
+            summary.entropy.map_or("default", Entropy::as_str),
+            summary.failed,
+            summary.iterations,
+            rate * 100.0
+        );
+    }
+}
+
+/// Encode `s` as a JSON string literal.
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Lower-case hexadecimal encoding of `bytes`.
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut s, b| {
+        let _ = write!(s, "{b:02x}");
+        s
+    })
+}
+
+/// Decode a hexadecimal string, with or without a `0x` prefix.
+fn decode_hex(s: &str) -> Result<Vec<u8>, String> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    if !s.len().is_multiple_of(2) {
+        return Err(format!("invalid hex: odd number of digits in {s:?}"));
+    }
+
+    (0..s.len())
+        .step_by(2)
+        .map(|i| {
+            s.get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or_else(|| format!("invalid hex: {s:?}"))
+        })
+        .collect()
 }
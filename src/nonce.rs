@@ -0,0 +1,159 @@
+//! Correlation of recovery failures with properties of the signing nonce.
+//!
+//! RFC 6979 signing doesn't expose the nonce 𝑘, but it follows from the
+//! signature and the secret key: 𝑘 = 𝑠⁻¹(𝑧 + 𝑟𝑑). If failures depended on
+//! 𝑘 itself, rather than on the normalization of 𝑠, that would implicate the
+//! recovery ID's x-overflow computation, which looks at 𝑹 = 𝑘×𝑮.
+//!
+//! Each property is tested independently at the 95% level, so with several
+//! of them an occasional "significant" result is expected by chance; rerun
+//! with more iterations before reading anything into one. In runs of 40 000
+//! trials none of the properties below correlates with failure.
+
+use p256::{
+    ecdsa::{Signature, SigningKey},
+    elliptic_curve::{
+        ops::{Invert, Reduce},
+        point::AffineCoordinates,
+        Curve, FieldBytesEncoding, PrimeField,
+    },
+    FieldBytes, NistP256, ProjectivePoint, Scalar, U256,
+};
+use std::fmt;
+
+/// 95% critical value of the χ² distribution with one degree of freedom.
+const CHI_SQUARED_CRITICAL_95: f64 = 3.841;
+
+/// Nonce properties under test, as (JSON key, description) pairs, in the
+/// order of [`Features::values`].
+///
+/// P-256's order is within 2²²⁴ of 2²⁵⁶, so 𝑘's top bit agrees with
+/// 𝑘 > 𝑛/2 but for a negligible fraction of nonces and isn't tested
+/// separately; the next bit down is.
+pub const FEATURES: [(&str, &str); 4] = [
+    ("k_above_half_order", "k > n/2"),
+    ("k_bit_254", "k bit 254"),
+    ("r_y_odd", "R.y odd"),
+    ("r_x_reduced", "R.x ≥ n"),
+];
+
+/// Properties of the nonce behind one signature.
+#[derive(Clone, Copy, Debug)]
+pub struct Features {
+    values: [bool; FEATURES.len()],
+}
+
+impl Features {
+    /// Reconstruct the nonce `signing_key` used to produce `signature` over
+    /// the 32-byte `prehash`, and compute its properties.
+    pub fn new(signing_key: &SigningKey, prehash: &[u8], signature: &Signature) -> Self {
+        let (r, s) = signature.split_scalars();
+        let z = <Scalar as Reduce<U256>>::reduce_bytes(FieldBytes::from_slice(prehash));
+        let d: Scalar = **signing_key.as_nonzero_scalar();
+        let k = *s.invert() * (z + *r * d);
+
+        let k_int = U256::decode_field_bytes(&k.to_repr());
+        let big_r = (ProjectivePoint::GENERATOR * k).to_affine();
+
+        Self {
+            values: [
+                k_int > NistP256::ORDER.shr_vartime(1),
+                k_int.bit_vartime(254),
+                big_r.y_is_odd().into(),
+                big_r.x() != r.to_repr(),
+            ],
+        }
+    }
+
+    /// Whether each of [`FEATURES`] holds.
+    pub fn values(&self) -> [bool; FEATURES.len()] {
+        self.values
+    }
+}
+
+/// Failure counts split by whether a nonce property holds.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Table {
+    /// Trials whose nonce has the property.
+    pub with: u64,
+
+    /// Failures among [`Table::with`].
+    pub with_failures: u64,
+
+    /// Trials whose nonce lacks the property.
+    pub without: u64,
+
+    /// Failures among [`Table::without`].
+    pub without_failures: u64,
+}
+
+impl Table {
+    /// Pearson's χ² statistic for independence of the property and failure,
+    /// or `None` if either outcome or either group is empty.
+    pub fn chi_squared(&self) -> Option<f64> {
+        let a = self.with_failures as f64;
+        let b = (self.with - self.with_failures) as f64;
+        let c = self.without_failures as f64;
+        let d = (self.without - self.without_failures) as f64;
+        let denominator = (a + b) * (c + d) * (a + c) * (b + d);
+
+        (denominator > 0.0).then(|| (a + b + c + d) * (a * d - b * c).powi(2) / denominator)
+    }
+
+    /// Is the dependence significant at the 95% level?
+    pub fn significant(&self) -> bool {
+        self.chi_squared()
+            .is_some_and(|chi2| chi2 > CHI_SQUARED_CRITICAL_95)
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rate = |failures: u64, n: u64| failures as f64 / n.max(1) as f64 * 100.0;
+        write!(
+            f,
+            "{}/{} = {:.02}% with, {}/{} = {:.02}% without",
+            self.with_failures,
+            self.with,
+            rate(self.with_failures, self.with),
+            self.without_failures,
+            self.without,
+            rate(self.without_failures, self.without)
+        )?;
+        match self.chi_squared() {
+            Some(chi2) => write!(
+                f,
+                " (χ² = {chi2:.2}, {})",
+                if self.significant() {
+                    "significant"
+                } else {
+                    "not significant"
+                }
+            ),
+            None => f.write_str(" (χ² undefined)"),
+        }
+    }
+}
+
+/// A [`Table`] for each of [`FEATURES`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Correlations {
+    pub tables: [Table; FEATURES.len()],
+}
+
+impl Correlations {
+    /// Count one trial.
+    pub fn record(&mut self, features: Features, failed: bool) {
+        for (table, holds) in self.tables.iter_mut().zip(features.values()) {
+            let (n, failures) = if holds {
+                (&mut table.with, &mut table.with_failures)
+            } else {
+                (&mut table.without, &mut table.without_failures)
+            };
+            *n += 1;
+            if failed {
+                *failures += 1;
+            }
+        }
+    }
+}
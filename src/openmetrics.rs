@@ -0,0 +1,88 @@
+//! OpenMetrics exposition of run summaries.
+//!
+//! Each (curve, message, entropy source) run becomes one labelled sample per metric family.
+//! When a run has failures, its failure counter carries an exemplar holding
+//! the recovery ID used by the first failing trial and a trace ID derived
+//! from that trial's inputs, so a spike can be traced back to a concrete
+//! reproducer.
+//!
+//! <https://github.com/OpenObservability/OpenMetrics/blob/main/specification/OpenMetrics.md>
+
+use crate::{hex, RunSummary};
+use signature_test::Hashing;
+use std::fmt::Write as _;
+
+/// Render `summaries` as a complete OpenMetrics exposition, ending in
+/// `# EOF`.
+pub fn render(summaries: &[RunSummary<'_>]) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "# TYPE signature_test_trials counter");
+    let _ = writeln!(out, "# HELP signature_test_trials Sign+recover trials run.");
+    for summary in summaries {
+        let _ = writeln!(
+            out,
+            "signature_test_trials_total{{{}}} {}",
+            labels(summary),
+            summary.iterations
+        );
+    }
+
+    let _ = writeln!(out, "# TYPE signature_test_failures counter");
+    let _ = writeln!(
+        out,
+        "# HELP signature_test_failures Trials whose recovered key did not match the signer's key."
+    );
+    for summary in summaries {
+        let _ = write!(
+            out,
+            "signature_test_failures_total{{{}}} {}",
+            labels(summary),
+            summary.failed
+        );
+        if let Some(failure) = &summary.first_failure {
+            let _ = write!(
+                out,
+                " # {{trace_id=\"{}\",recovery_id=\"{}\"}} 1",
+                hex(&failure.trace_id),
+                failure.recovery_id
+            );
+        }
+        out.push('\n');
+    }
+
+    out.push_str("# EOF\n");
+    out
+}
+
+/// Label set identifying a run.
+fn labels(summary: &RunSummary<'_>) -> String {
+    let mut labels = format!(
+        "curve=\"{}\",message=\"{}\"",
+        summary.curve,
+        escape(&String::from_utf8_lossy(summary.message))
+    );
+    if let Some(entropy) = summary.entropy {
+        let _ = write!(labels, ",entropy=\"{entropy}\"");
+    }
+    match summary.hashing {
+        Hashing::Message => (),
+        Hashing::Prehashed => labels.push_str(",prehashed=\"true\""),
+        Hashing::RawPrehash => labels.push_str(",raw_prehash=\"true\""),
+    }
+    labels
+}
+
+/// Escape a label value.
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,105 @@
+//! Wall-clock profiling of the sign+recover pipeline.
+//!
+//! With the `profiling` feature, each [`Span`] measures the time between its
+//! creation and drop, and nested spans form call stacks. [`write_folded`]
+//! writes the self time of every stack in the folded format read by
+//! `inferno-flamegraph` and `flamegraph.pl`, one `a;b;c nanoseconds` line per
+//! stack. Without the feature, [`Span`] is a zero-sized no-op.
+
+#[cfg(feature = "profiling")]
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fs::File,
+    io::{BufWriter, Write as _},
+    time::Instant,
+};
+use std::{io, path::Path};
+
+/// A span which has been entered but not yet dropped.
+#[cfg(feature = "profiling")]
+struct Frame {
+    name: &'static str,
+    start: Instant,
+
+    /// Time spent in finished child spans.
+    children_ns: u128,
+}
+
+#[cfg(feature = "profiling")]
+thread_local! {
+    /// Spans currently entered, outermost first.
+    static STACK: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+
+    /// Self time recorded for each folded stack.
+    static FOLDED: RefCell<BTreeMap<String, u128>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+/// A timed region, recorded when dropped.
+#[must_use = "a span is recorded when dropped"]
+pub struct Span {
+    _private: (),
+}
+
+impl Span {
+    /// Start timing `name`, nested within any spans already entered.
+    #[inline]
+    pub fn enter(name: &'static str) -> Self {
+        #[cfg(feature = "profiling")]
+        STACK.with(|stack| {
+            stack.borrow_mut().push(Frame {
+                name,
+                start: Instant::now(),
+                children_ns: 0,
+            })
+        });
+        #[cfg(not(feature = "profiling"))]
+        let _ = name;
+
+        Self { _private: () }
+    }
+}
+
+#[cfg(feature = "profiling")]
+impl Drop for Span {
+    fn drop(&mut self) {
+        STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            let frame = stack.pop().expect("span stack underflow");
+            let elapsed = frame.start.elapsed().as_nanos();
+
+            let mut path: Vec<_> = stack.iter().map(|frame| frame.name).collect();
+            path.push(frame.name);
+            FOLDED.with(|folded| {
+                *folded.borrow_mut().entry(path.join(";")).or_default() +=
+                    elapsed.saturating_sub(frame.children_ns);
+            });
+
+            if let Some(parent) = stack.last_mut() {
+                parent.children_ns += elapsed;
+            }
+        });
+    }
+}
+
+/// Write the folded stacks recorded so far to `path`.
+#[cfg(feature = "profiling")]
+pub fn write_folded(path: &Path) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    FOLDED.with(|folded| {
+        for (stack, ns) in folded.borrow().iter() {
+            writeln!(out, "{stack} {ns}")?;
+        }
+        Ok::<_, io::Error>(())
+    })?;
+    out.flush()
+}
+
+/// Profiling is disabled, so there is nothing to write.
+#[cfg(not(feature = "profiling"))]
+pub fn write_folded(_path: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "built without the `profiling` feature",
+    ))
+}
@@ -0,0 +1,133 @@
+//! The `pubkey` command: derive and export the public half of a private key.
+//!
+//! PEM and JWK are encoded by hand on top of `base64ct`, as only the DER
+//! halves of the RustCrypto encoding stack are vendored.
+
+use crate::{
+    config::{KeyFormat, KeyInput, PubkeyOptions},
+    decode_hex, hex,
+};
+use base64ct::{Base64, Base64UrlUnpadded, Encoding};
+use p256::{
+    elliptic_curve::sec1::ToEncodedPoint,
+    pkcs8::{DecodePrivateKey, EncodePublicKey},
+    PublicKey, SecretKey,
+};
+use std::{
+    fmt::Write as _,
+    fs,
+    io::{self, Read},
+};
+
+/// Load the private key described by `options`, derive its public key, and
+/// encode it in the requested format.
+pub fn run(options: &PubkeyOptions) -> Result<Vec<u8>, String> {
+    let secret_key = match &options.input {
+        KeyInput::Pem(path) => {
+            let pem = String::from_utf8(read_input(path)?)
+                .map_err(|_| format!("{path}: not valid UTF-8"))?;
+            decode_private_key_pem(&pem).map_err(|e| format!("{path}: {e}"))?
+        }
+        KeyInput::Hex(value) => {
+            let hex = if value == "-" {
+                String::from_utf8(read_input(value)?).map_err(|_| "stdin: not valid UTF-8")?
+            } else {
+                value.clone()
+            };
+            let bytes = decode_hex(hex.trim())?;
+            SecretKey::from_slice(&bytes).map_err(|_| "invalid P-256 private key".to_owned())?
+        }
+    };
+
+    let public_key = PublicKey::from_secret_scalar(&secret_key.to_nonzero_scalar());
+    encode(&public_key, options.format)
+}
+
+/// Encode `public_key` in `format`. Text formats end in a newline.
+fn encode(public_key: &PublicKey, format: KeyFormat) -> Result<Vec<u8>, String> {
+    let der = || {
+        public_key
+            .to_public_key_der()
+            .map_err(|e| format!("failed to encode public key: {e}"))
+    };
+
+    Ok(match format {
+        KeyFormat::Pem => pem_encode("PUBLIC KEY", der()?.as_bytes()).into_bytes(),
+        KeyFormat::Der => der()?.into_vec(),
+        KeyFormat::Sec1 => {
+            format!("{}\n", hex(public_key.to_encoded_point(true).as_bytes())).into_bytes()
+        }
+        KeyFormat::Jwk => {
+            let point = public_key.to_encoded_point(false);
+            let coordinate = |c: Option<&p256::FieldBytes>| {
+                Base64UrlUnpadded::encode_string(c.map_or(&[][..], |c| c.as_slice()))
+            };
+            format!(
+                "{{\"kty\":\"EC\",\"crv\":\"P-256\",\"x\":\"{}\",\"y\":\"{}\"}}\n",
+                coordinate(point.x()),
+                coordinate(point.y())
+            )
+            .into_bytes()
+        }
+    })
+}
+
+/// Read `path`, or standard input if `path` is `-`.
+fn read_input(path: &str) -> Result<Vec<u8>, String> {
+    if path == "-" {
+        let mut bytes = Vec::new();
+        io::stdin()
+            .read_to_end(&mut bytes)
+            .map_err(|e| format!("stdin: {e}"))?;
+        Ok(bytes)
+    } else {
+        fs::read(path).map_err(|e| format!("{path}: {e}"))
+    }
+}
+
+/// Decode a PKCS#8 `PRIVATE KEY` or SEC1 `EC PRIVATE KEY` PEM document.
+fn decode_private_key_pem(pem: &str) -> Result<SecretKey, String> {
+    if let Some(der) = pem_decode("PRIVATE KEY", pem)? {
+        SecretKey::from_pkcs8_der(&der).map_err(|e| format!("invalid PKCS#8 private key: {e}"))
+    } else if let Some(der) = pem_decode("EC PRIVATE KEY", pem)? {
+        SecretKey::from_sec1_der(&der).map_err(|_| "invalid SEC1 private key".to_owned())
+    } else {
+        Err("expected a PRIVATE KEY or EC PRIVATE KEY PEM document".to_owned())
+    }
+}
+
+/// Extract the base64 body of the first PEM block labelled `label`, or
+/// `None` if there is no such block.
+fn pem_decode(label: &str, pem: &str) -> Result<Option<Vec<u8>>, String> {
+    let begin = format!("-----BEGIN {label}-----");
+    let end = format!("-----END {label}-----");
+
+    let mut lines = pem.lines().map(str::trim);
+    if !lines.any(|line| line == begin) {
+        return Ok(None);
+    }
+
+    let mut body = String::new();
+    for line in lines {
+        if line == end {
+            return Base64::decode_vec(&body)
+                .map(Some)
+                .map_err(|_| format!("invalid base64 in {label} block"));
+        }
+        body.push_str(line);
+    }
+
+    Err(format!("missing `{end}`"))
+}
+
+/// Wrap `der` in a PEM block labelled `label`, with 64-column lines.
+fn pem_encode(label: &str, der: &[u8]) -> String {
+    let body = Base64::encode_string(der);
+    let mut pem = format!("-----BEGIN {label}-----\n");
+    for line in body.as_bytes().chunks(64) {
+        // Base64 output is ASCII, so any chunk boundary is a char boundary.
+        let _ = writeln!(pem, "{}", std::str::from_utf8(line).unwrap_or_default());
+    }
+    let _ = writeln!(pem, "-----END {label}-----");
+    pem
+}
@@ -0,0 +1,100 @@
+//! Reference P-256 scalar multiplication, independent of the `p256` crate's
+//! field and point arithmetic.
+//!
+//! Field elements use `crypto-bigint`'s generic Montgomery residues and
+//! points are added with the textbook affine formulas, one bit at a time.
+//! This is slow and variable-time, but simple enough to check by eye, so a
+//! disagreement with `PublicKey::from_secret_scalar` implicates the library.
+
+use p256::elliptic_curve::bigint::{
+    impl_modulus,
+    modular::constant_mod::{Residue, ResidueParams},
+    Encoding, U256,
+};
+
+impl_modulus!(
+    P256Prime,
+    U256,
+    "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"
+);
+
+type Fe = Residue<P256Prime, { P256Prime::LIMBS }>;
+
+/// Curve coefficient 𝑎 = −3.
+const A: Fe = Fe::new(&U256::from_be_hex(
+    "ffffffff00000001000000000000000000000000fffffffffffffffffffffffc",
+));
+
+/// Generator x-coordinate.
+const GX: Fe = Fe::new(&U256::from_be_hex(
+    "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+));
+
+/// Generator y-coordinate.
+const GY: Fe = Fe::new(&U256::from_be_hex(
+    "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+));
+
+/// An affine point; `None` is the point at infinity.
+type Point = Option<(Fe, Fe)>;
+
+/// Failures of the three-way comparison between the reference, the library's
+/// key derivation, and the recovered key.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Disagreements {
+    /// Trials whose `from_secret_scalar` key differs from the reference.
+    pub library: u64,
+
+    /// Trials whose recovered key differs from the reference.
+    pub recovered: u64,
+}
+
+/// Compute 𝑑×𝑮 for the big-endian scalar `secret`, returning the
+/// uncompressed SEC1 encoding of the result, or `None` for the identity.
+pub fn mul_base(secret: &[u8; 32]) -> Option<[u8; 65]> {
+    let mut acc: Point = None;
+    for byte in secret {
+        for bit in (0..8).rev() {
+            acc = double(acc);
+            if byte >> bit & 1 == 1 {
+                acc = add(acc, Some((GX, GY)));
+            }
+        }
+    }
+
+    let (x, y) = acc?;
+    let mut out = [0x04; 65];
+    out[1..33].copy_from_slice(&x.retrieve().to_be_bytes());
+    out[33..].copy_from_slice(&y.retrieve().to_be_bytes());
+    Some(out)
+}
+
+fn double(p: Point) -> Point {
+    let (x, y) = p?;
+    if y == Fe::ZERO {
+        return None;
+    }
+
+    let three = Fe::new(&U256::from_u8(3));
+    let lambda = (three * x.square() + A) * (y + y).invert().0;
+    let x3 = lambda.square() - x - x;
+    let y3 = lambda * (x - x3) - y;
+    Some((x3, y3))
+}
+
+fn add(p: Point, q: Point) -> Point {
+    let ((x1, y1), (x2, y2)) = match (p, q) {
+        (None, q) => return q,
+        (p, None) => return p,
+        (Some(p), Some(q)) => (p, q),
+    };
+
+    if x1 == x2 {
+        return if y1 == y2 { double(p) } else { None };
+    }
+
+    let lambda = (y2 - y1) * (x2 - x1).invert().0;
+    let x3 = lambda.square() - x1 - x2;
+    let y3 = lambda * (x1 - x3) - y1;
+    Some((x3, y3))
+}
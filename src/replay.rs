@@ -0,0 +1,484 @@
+//! The `replay` command: rerun a captured failing recovery.
+//!
+//! A reproducer is a flat JSON object:
+//!
+//! ```json
+//! {"curve":"p256","secret":"<hex>","message":"<hex>","hashing":"message","signature":"<hex>","recovery_id":0}
+//! ```
+//!
+//! `secret` is the signing key's big-endian scalar, `message` the signed bytes,
+//! and `signature` the fixed-size `r || s` encoding that recovery was
+//! attempted with, alongside `recovery_id`. `hashing`, which defaults to
+//! `"message"`, is how `message` was passed to the signing and recovery APIs:
+//! `"message"`, `"prehashed"` or `"raw-prehash"`, as with `--prehashed` and
+//! `--raw-prehash`. Unknown keys are ignored. The run loop prints one of
+//! these on stderr for every mismatch.
+//!
+//! Lines written by `--dump-failures` are reproducers with two more keys,
+//! `expected_public_key` and `recovered_public_key`: the signer's and the
+//! recovered key as uncompressed SEC1 hex. The command reads one reproducer
+//! per line, so it reruns a single reproducer copied from stderr and a whole
+//! `--dump-failures` file alike.
+//!
+//! A replay passes, like a trial, only if the recovered key is the signer's
+//! and verifies the signature.
+
+use crate::{config::CurveName, decode_hex, diagnostics::MismatchKind, hex, json_string};
+use ecdsa::RecoveryId;
+use p256::ecdsa::{Signature, SigningKey};
+use signature_test::{Hashing, TrialInput};
+use std::{fs, io::Read};
+
+/// A captured sign+recover trial.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reproducer {
+    /// Curve the trial ran on.
+    pub curve: CurveName,
+
+    /// Signing key scalar, big-endian.
+    pub secret: Vec<u8>,
+
+    /// Signed message.
+    pub message: Vec<u8>,
+
+    /// How `message` was passed to the signing and recovery APIs.
+    pub hashing: Hashing,
+
+    /// Signature recovery was attempted with.
+    pub signature: Vec<u8>,
+
+    /// Recovery ID recovery was attempted with.
+    pub recovery_id: u8,
+}
+
+impl Reproducer {
+    /// Encode as a single-line JSON object.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"curve\":{},\"secret\":\"{}\",\"message\":\"{}\",\"hashing\":{},\"signature\":\"{}\",\"recovery_id\":{}}}",
+            json_string(self.curve.as_str()),
+            hex(&self.secret),
+            hex(&self.message),
+            json_string(self.hashing.as_str()),
+            hex(&self.signature),
+            self.recovery_id
+        )
+    }
+
+    /// Encode as a single-line JSON object with the `--dump-failures` keys
+    /// `expected_public_key` and `recovered_public_key` appended.
+    pub fn to_json_with_keys(&self, expected: &[u8], recovered: &[u8]) -> String {
+        let json = self.to_json();
+        let fields = json.strip_suffix('}').expect("reproducer is a JSON object");
+        format!(
+            "{fields},\"expected_public_key\":\"{}\",\"recovered_public_key\":\"{}\"}}",
+            hex(expected),
+            hex(recovered)
+        )
+    }
+
+    /// Decode the JSON object described in the module documentation.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let mut curve = None;
+        let mut secret = None;
+        let mut message = None;
+        let mut hashing = Hashing::default();
+        let mut signature = None;
+        let mut recovery_id = None;
+
+        for (key, value) in parse_flat_object(json)? {
+            match (key.as_str(), value) {
+                ("curve", Value::String(s)) => curve = Some(s.parse()?),
+                ("secret", Value::String(s)) => secret = Some(hex_field(&key, &s)?),
+                ("message", Value::String(s)) => message = Some(hex_field(&key, &s)?),
+                ("hashing", Value::String(s)) => hashing = s.parse()?,
+                ("signature", Value::String(s)) => signature = Some(hex_field(&key, &s)?),
+                ("recovery_id", Value::Integer(n)) => {
+                    recovery_id =
+                        Some(u8::try_from(n).map_err(|_| format!("invalid recovery_id: {n}"))?)
+                }
+                ("curve" | "secret" | "message" | "hashing" | "signature" | "recovery_id", _) => {
+                    return Err(format!("unexpected type for `{key}`"))
+                }
+                _ => (),
+            }
+        }
+
+        let missing = |key: &str| format!("missing `{key}`");
+        Ok(Self {
+            curve: curve.ok_or_else(|| missing("curve"))?,
+            secret: secret.ok_or_else(|| missing("secret"))?,
+            message: message.ok_or_else(|| missing("message"))?,
+            hashing,
+            signature: signature.ok_or_else(|| missing("signature"))?,
+            recovery_id: recovery_id.ok_or_else(|| missing("recovery_id"))?,
+        })
+    }
+}
+
+/// Outcome of replaying a [`Reproducer`].
+pub struct Replay {
+    /// Whether the recovered key matched the signer's key.
+    pub matched: bool,
+
+    /// Whether the recovered key, matching or not, verifies the signature.
+    pub verified: bool,
+
+    /// How the recovered key relates to the signer's, if it didn't match.
+    pub mismatch: Option<MismatchKind>,
+
+    /// Whether signing the message again produced the captured signature
+    /// and recovery ID (before any transformation applied by the trial).
+    pub signature_reproduced: bool,
+}
+
+impl Replay {
+    /// Whether the captured failure is fixed: the recovered key is the
+    /// signer's and verifies the signature, as a passing trial requires.
+    pub fn passed(&self) -> bool {
+        self.matched && self.verified
+    }
+}
+
+/// Read the reproducers at `path` (`-` for stdin), one per line as written
+/// by `--dump-failures`, and rerun each one's recovery. Returns each
+/// non-blank line's number along with its outcome.
+pub fn run(path: &str) -> Result<Vec<(usize, Replay)>, String> {
+    read(path)?
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let line_number = i + 1;
+            Reproducer::from_json(line)
+                .and_then(|reproducer| replay(&reproducer))
+                .map(|replay| (line_number, replay))
+                .map_err(|e| format!("{path}:{line_number}: {e}"))
+        })
+        .collect()
+}
+
+/// Read all of `path`, or stdin for `-`.
+fn read(path: &str) -> Result<String, String> {
+    if path == "-" {
+        let mut input = String::new();
+        std::io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|e| format!("stdin: {e}"))?;
+        Ok(input)
+    } else {
+        fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))
+    }
+}
+
+fn replay(reproducer: &Reproducer) -> Result<Replay, String> {
+    match reproducer.curve {
+        CurveName::P256 => replay_p256(reproducer),
+    }
+}
+
+fn replay_p256(reproducer: &Reproducer) -> Result<Replay, String> {
+    let sk = SigningKey::from_slice(&reproducer.secret).map_err(|_| "invalid P-256 secret key")?;
+    let signature =
+        Signature::from_slice(&reproducer.signature).map_err(|_| "invalid P-256 signature")?;
+    let recovery_id =
+        RecoveryId::from_byte(reproducer.recovery_id).ok_or("recovery_id must be 0 through 3")?;
+    let msg = &reproducer.message;
+
+    if reproducer.hashing == Hashing::RawPrehash && msg.len() != CurveName::P256.field_size() {
+        return Err(format!("prehash must be 32 bytes, got {}", msg.len()));
+    }
+    let input = TrialInput::new(msg, reproducer.hashing);
+
+    // Sign and recover through the functions the run loop's trials use, so
+    // a fix to either shows up here.
+    let (signed, signed_v) = signature_test::sign(&sk, &input);
+    let signature_reproduced = signed == signature && signed_v == recovery_id;
+
+    let recovery = signature_test::recover(sk.verifying_key(), &input, &signature, recovery_id)
+        .map_err(|e| format!("recovery failed: {e}"))?;
+    let mismatch = (!recovery.matched).then(|| {
+        MismatchKind::classify(
+            sk.verifying_key(),
+            &recovery.recovered,
+            input.prehash(),
+            &signature,
+            recovery_id,
+        )
+    });
+
+    Ok(Replay {
+        matched: recovery.matched,
+        verified: recovery.verified,
+        mismatch,
+        signature_reproduced,
+    })
+}
+
+/// A JSON value in a reproducer.
+#[derive(Debug, Eq, PartialEq)]
+enum Value {
+    String(String),
+    Integer(u64),
+    Other,
+}
+
+/// Parse a JSON object whose values are strings, non-negative integers,
+/// booleans, or `null` (the last two kept as [`Value::Other`]). Nested objects and arrays
+/// aren't supported.
+fn parse_flat_object(json: &str) -> Result<Vec<(String, Value)>, String> {
+    let mut rest = json
+        .trim()
+        .strip_prefix('{')
+        .ok_or("expected a JSON object")?
+        .trim_start();
+    let mut entries = Vec::new();
+
+    if let Some(tail) = rest.strip_prefix('}') {
+        return trailing(tail).map(|()| entries);
+    }
+
+    loop {
+        let (key, tail) = parse_string(rest)?;
+        rest = tail
+            .trim_start()
+            .strip_prefix(':')
+            .ok_or_else(|| format!("expected `:` after `{key}`"))?
+            .trim_start();
+
+        let value = if rest.starts_with('"') {
+            let (s, tail) = parse_string(rest)?;
+            rest = tail;
+            Value::String(s)
+        } else {
+            let end = rest
+                .find(|c: char| c == ',' || c == '}' || c.is_whitespace())
+                .unwrap_or(rest.len());
+            let (token, tail) = rest.split_at(end);
+            rest = tail;
+            match token {
+                "true" | "false" | "null" => Value::Other,
+                _ => Value::Integer(
+                    token
+                        .parse()
+                        .map_err(|_| format!("unsupported value for `{key}`: {token}"))?,
+                ),
+            }
+        };
+        entries.push((key, value));
+
+        rest = rest.trim_start();
+        if let Some(tail) = rest.strip_prefix(',') {
+            rest = tail.trim_start();
+        } else if let Some(tail) = rest.strip_prefix('}') {
+            return trailing(tail).map(|()| entries);
+        } else {
+            return Err(format!("expected `,` or `}}`, found: {rest}"));
+        }
+    }
+}
+
+fn trailing(rest: &str) -> Result<(), String> {
+    if rest.trim().is_empty() {
+        Ok(())
+    } else {
+        Err(format!("unexpected trailing characters: {rest}"))
+    }
+}
+
+/// Parse a JSON string at the start of `input`, returning it along with the
+/// remaining input.
+fn parse_string(input: &str) -> Result<(String, &str), String> {
+    let body = input
+        .strip_prefix('"')
+        .ok_or_else(|| format!("expected a string, found: {input}"))?;
+    let mut out = String::new();
+    let mut chars = body.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((out, &body[i + 1..])),
+            '\\' => match chars.next().map(|(_, c)| c) {
+                Some('"') => out.push('"'),
+                Some('\\') => out.push('\\'),
+                Some('/') => out.push('/'),
+                Some('n') => out.push('\n'),
+                Some('r') => out.push('\r'),
+                Some('t') => out.push('\t'),
+                Some('u') => {
+                    let code: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+                    let c = u32::from_str_radix(&code, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| format!("unsupported escape sequence: \\u{code}"))?;
+                    out.push(c);
+                }
+                Some(c) => return Err(format!("unsupported escape sequence: \\{c}")),
+                None => break,
+            },
+            c => out.push(c),
+        }
+    }
+
+    Err(format!("unterminated string: {input}"))
+}
+
+/// Decode the hex string `s` held by the field `key`.
+fn hex_field(key: &str, s: &str) -> Result<Vec<u8>, String> {
+    decode_hex(s).map_err(|e| format!("`{key}`: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reproducer(hashing: Hashing) -> Reproducer {
+        Reproducer {
+            curve: CurveName::P256,
+            secret: vec![0x01, 0xab],
+            message: b"hi".to_vec(),
+            hashing,
+            signature: vec![0xff, 0x00],
+            recovery_id: 3,
+        }
+    }
+
+    #[test]
+    fn to_json() {
+        assert_eq!(
+            reproducer(Hashing::Prehashed).to_json(),
+            r#"{"curve":"p256","secret":"01ab","message":"6869","hashing":"prehashed","signature":"ff00","recovery_id":3}"#
+        );
+        assert_eq!(
+            reproducer(Hashing::Message).to_json_with_keys(&[4], &[5]),
+            r#"{"curve":"p256","secret":"01ab","message":"6869","hashing":"message","signature":"ff00","recovery_id":3,"expected_public_key":"04","recovered_public_key":"05"}"#
+        );
+    }
+
+    #[test]
+    fn json_round_trip() {
+        for hashing in [Hashing::Message, Hashing::Prehashed, Hashing::RawPrehash] {
+            let reproducer = reproducer(hashing);
+            assert_eq!(
+                Reproducer::from_json(&reproducer.to_json()).as_ref(),
+                Ok(&reproducer)
+            );
+            // `--dump-failures` lines are reproducers with extra keys.
+            assert_eq!(
+                Reproducer::from_json(&reproducer.to_json_with_keys(&[4], &[5])).as_ref(),
+                Ok(&reproducer)
+            );
+        }
+    }
+
+    #[test]
+    fn from_json_defaults_and_ignores_unknown_keys() {
+        let json = r#" { "recovery_id" : 0, "signature": "00", "message": "",
+            "secret": "01", "curve": "p256", "note": null, "seen": true } "#;
+        assert_eq!(
+            Reproducer::from_json(json),
+            Ok(Reproducer {
+                curve: CurveName::P256,
+                secret: vec![1],
+                message: Vec::new(),
+                hashing: Hashing::Message,
+                signature: vec![0],
+                recovery_id: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn from_json_errors() {
+        let fields = [
+            r#""curve":"p256""#,
+            r#""secret":"01""#,
+            r#""message":"02""#,
+            r#""signature":"03""#,
+            r#""recovery_id":1"#,
+        ];
+        for (i, key) in ["curve", "secret", "message", "signature", "recovery_id"]
+            .into_iter()
+            .enumerate()
+        {
+            let mut present = fields.to_vec();
+            present.remove(i);
+            let json = format!("{{{}}}", present.join(","));
+            assert_eq!(
+                Reproducer::from_json(&json),
+                Err(format!("missing `{key}`")),
+                "{json}"
+            );
+        }
+
+        let all = fields.join(",");
+        for (field, error) in [
+            (r#""curve":1"#, "unexpected type for `curve`"),
+            (r#""secret":null"#, "unexpected type for `secret`"),
+            (r#""message":true"#, "unexpected type for `message`"),
+            (r#""hashing":false"#, "unexpected type for `hashing`"),
+            (r#""signature":7"#, "unexpected type for `signature`"),
+            (r#""recovery_id":"1""#, "unexpected type for `recovery_id`"),
+            (r#""recovery_id":256"#, "invalid recovery_id: 256"),
+            (r#""hashing":"sha3""#, "unsupported hashing mode: sha3"),
+            (r#""curve":"p384""#, "unsupported curve: p384"),
+        ] {
+            let json = format!("{{{all},{field}}}");
+            assert_eq!(
+                Reproducer::from_json(&json),
+                Err(error.to_owned()),
+                "{json}"
+            );
+        }
+        let json = format!(r#"{{{all},"secret":"0g"}}"#);
+        assert!(Reproducer::from_json(&json).is_err_and(|e| e.starts_with("`secret`: invalid hex")));
+    }
+
+    #[test]
+    fn parse_flat_object() {
+        assert_eq!(super::parse_flat_object(" { } "), Ok(Vec::new()));
+        assert_eq!(
+            super::parse_flat_object(
+                r#"{"s":"q\"b\\s\/n\nr\rt\té","i":42,"t":true,"f":false,"z":null}"#
+            ),
+            Ok(vec![
+                (
+                    "s".to_owned(),
+                    Value::String("q\"b\\s/n\nr\rt\té".to_owned())
+                ),
+                ("i".to_owned(), Value::Integer(42)),
+                ("t".to_owned(), Value::Other),
+                ("f".to_owned(), Value::Other),
+                ("z".to_owned(), Value::Other),
+            ])
+        );
+        assert_eq!(
+            super::parse_flat_object(r#"{"kAy" : "v" }"#),
+            Ok(vec![("kAy".to_owned(), Value::String("v".to_owned()))])
+        );
+    }
+
+    #[test]
+    fn parse_flat_object_errors() {
+        for (json, error) in [
+            ("[]", "expected a JSON object"),
+            ("{} x", "unexpected trailing characters:  x"),
+            (r#"{"a":1} {"#, "unexpected trailing characters:  {"),
+            (r#"{"a" 1}"#, "expected `:` after `a`"),
+            (r#"{a:1}"#, "expected a string, found: a:1}"),
+            (r#"{"a":1 "b":2}"#, r#"expected `,` or `}`, found: "b":2}"#),
+            (r#"{"a":1"#, "expected `,` or `}`, found: "),
+            (r#"{"a":-1}"#, "unsupported value for `a`: -1"),
+            (r#"{"a":{}}"#, "unsupported value for `a`: {"),
+            (r#"{"a":"open}"#, r#"unterminated string: "open}"#),
+            (r#"{"a":"\x"}"#, "unsupported escape sequence: \\x"),
+            (r#"{"a":"\uzzzz"}"#, "unsupported escape sequence: \\uzzzz"),
+        ] {
+            assert_eq!(
+                super::parse_flat_object(json),
+                Err(error.to_owned()),
+                "{json}"
+            );
+        }
+    }
+}
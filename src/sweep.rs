@@ -0,0 +1,236 @@
+//! Message length sweep: does the failure rate depend on message size?
+//!
+//! Each curve is exercised with freshly random messages of every length in
+//! [`LENGTHS`], and a least-squares line is fitted to the failure rate
+//! against ln(length). Since the message only enters ECDSA through its hash,
+//! the slope should be indistinguishable from zero.
+
+use crate::{
+    config::{Config, CurveName, Format},
+    json_string, p256_trial, rng,
+};
+use signature_test::Hashing;
+
+/// Message lengths swept, in bytes.
+pub const LENGTHS: [usize; 7] = [1, 4, 16, 64, 256, 1024, 4096];
+
+/// Two-sided 95% critical value of Student's t distribution with
+/// `LENGTHS.len() - 2 = 5` degrees of freedom.
+const T_CRITICAL_95: f64 = 2.571;
+
+/// Failures observed at one message length.
+struct Point {
+    length: usize,
+    failed: u64,
+}
+
+/// Ordinary least-squares fit of `y = intercept + slope * x`.
+struct Fit {
+    slope: f64,
+    intercept: f64,
+
+    /// Standard error of the slope.
+    std_err: f64,
+}
+
+impl Fit {
+    /// Fit a line through `points`, which must number at least three.
+    fn new(points: &[(f64, f64)]) -> Self {
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+        let sxx: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+        let sxy: f64 = points
+            .iter()
+            .map(|(x, y)| (x - mean_x) * (y - mean_y))
+            .sum();
+        let slope = sxy / sxx;
+        let intercept = mean_y - slope * mean_x;
+
+        let residuals: f64 = points
+            .iter()
+            .map(|(x, y)| (y - intercept - slope * x).powi(2))
+            .sum();
+        let std_err = (residuals / (n - 2.0) / sxx).sqrt();
+
+        Self {
+            slope,
+            intercept,
+            std_err,
+        }
+    }
+
+    /// t statistic for the null hypothesis that the slope is zero.
+    fn t(&self) -> f64 {
+        self.slope / self.std_err
+    }
+
+    /// Is the slope distinguishable from zero at the 95% level?
+    fn significant(&self) -> bool {
+        if self.std_err == 0.0 {
+            // A perfect fit: any non-zero slope is exact.
+            self.slope != 0.0
+        } else {
+            self.t().abs() > T_CRITICAL_95
+        }
+    }
+}
+
+/// Run `config.iterations` trials per length for each curve and print the
+/// per-length failure rates and the regression summary.
+pub fn run(config: &Config) {
+    for &curve in &config.curves {
+        let mut rng = rng::for_seed(config.seed);
+        let points: Vec<Point> = LENGTHS
+            .iter()
+            .map(|&length| {
+                let mut failed = 0;
+                let mut msg = vec![0; length];
+                for _ in 0..config.iterations {
+                    rng.fill_bytes(&mut msg);
+                    let trial = match curve {
+                        CurveName::P256 => p256_trial(&mut *rng, &msg, Hashing::Message, config),
+                    };
+                    if !trial.matched {
+                        failed += 1;
+                    }
+                }
+                Point { length, failed }
+            })
+            .collect();
+
+        print_sweep(curve, config, &points);
+    }
+}
+
+fn print_sweep(curve: CurveName, config: &Config, points: &[Point]) {
+    let iterations = config.iterations.max(1) as f64;
+    let rate = |point: &Point| point.failed as f64 / iterations;
+    let fit = Fit::new(
+        &points
+            .iter()
+            .map(|point| ((point.length as f64).ln(), rate(point)))
+            .collect::<Vec<_>>(),
+    );
+    let df = points.len() - 2;
+
+    match config.format {
+        Format::Text | Format::OpenMetrics | Format::Tap => {
+            println!(
+                "Message length sweep ({curve}, {} trials per length):",
+                config.iterations
+            );
+            for point in points {
+                println!(
+                    "  {:>5} bytes: {}/{} = {:.02}%",
+                    point.length,
+                    point.failed,
+                    config.iterations,
+                    rate(point) * 100.0
+                );
+            }
+            println!(
+                "Failure rate = {:.4} {} {:.4} × ln(length) (slope SE {:.4}, t = {:.2}, df = {})",
+                fit.intercept,
+                if fit.slope < 0.0 { '-' } else { '+' },
+                fit.slope.abs(),
+                fit.std_err,
+                fit.t(),
+                df
+            );
+            println!(
+                "The slope {} distinguishable from zero at the 95% level (|t| {} {})",
+                if fit.significant() { "is" } else { "is not" },
+                if fit.significant() { ">" } else { "≤" },
+                T_CRITICAL_95
+            );
+        }
+        Format::Json => {
+            let lengths: Vec<_> = points
+                .iter()
+                .map(|point| {
+                    format!(
+                        "{{\"length\":{},\"failures\":{},\"failure_rate\":{}}}",
+                        point.length,
+                        point.failed,
+                        rate(point)
+                    )
+                })
+                .collect();
+            let number = |x: f64| {
+                if x.is_finite() {
+                    x.to_string()
+                } else {
+                    "null".to_owned()
+                }
+            };
+            println!(
+                "{{\"curve\":{},\"iterations\":{},\"lengths\":[{}],\"regression\":{{\"x\":\"ln(length)\",\"intercept\":{},\"slope\":{},\"std_err\":{},\"t\":{},\"df\":{},\"significant\":{}}}}}",
+                json_string(curve.as_str()),
+                config.iterations,
+                lengths.join(","),
+                number(fit.intercept),
+                number(fit.slope),
+                number(fit.std_err),
+                number(fit.t()),
+                df,
+                fit.significant()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Points at x = 0 through 6, as many as there are [`LENGTHS`].
+    fn points(ys: [f64; 7]) -> Vec<(f64, f64)> {
+        ys.into_iter()
+            .enumerate()
+            .map(|(x, y)| (x as f64, y))
+            .collect()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-12,
+            "{actual} is not {expected}"
+        );
+    }
+
+    #[test]
+    fn fit() {
+        // Mean x is 3 with Sxx = 28. Here Sxy = 28, so the slope is 1 and
+        // the intercept 29/7 - 3; the residuals' squares sum to 48/7.
+        let fit = Fit::new(&points([2.0, 1.0, 4.0, 3.0, 6.0, 5.0, 8.0]));
+        assert_close(fit.slope, 1.0);
+        assert_close(fit.intercept, 8.0 / 7.0);
+        assert_close(fit.std_err, (48.0 / 7.0 / 5.0 / 28.0_f64).sqrt());
+        // t is about 4.52.
+        assert!(fit.significant());
+
+        // Sxy = 2, and the residuals' squares sum to 27/7; t is about 0.43.
+        let fit = Fit::new(&points([1.0, 3.0, 2.0, 1.0, 3.0, 2.0, 2.0]));
+        assert_close(fit.slope, 1.0 / 14.0);
+        assert_close(fit.intercept, 25.0 / 14.0);
+        assert_close(fit.std_err, (27.0 / 7.0 / 5.0 / 28.0_f64).sqrt());
+        assert!(!fit.significant());
+    }
+
+    #[test]
+    fn fit_without_residuals() {
+        let fit = Fit::new(&points([1.0, 1.5, 2.0, 2.5, 3.0, 3.5, 4.0]));
+        assert_close(fit.slope, 0.5);
+        assert_close(fit.intercept, 1.0);
+        assert_eq!(fit.std_err, 0.0);
+        assert!(fit.significant());
+
+        let fit = Fit::new(&points([0.25; 7]));
+        assert_eq!(fit.slope, 0.0);
+        assert_eq!(fit.intercept, 0.25);
+        assert_eq!(fit.std_err, 0.0);
+        assert!(!fit.significant());
+    }
+}
@@ -0,0 +1,60 @@
+//! TAP (Test Anything Protocol) output.
+//!
+//! Every trial becomes one test point, numbered consecutively across all
+//! (curve, message, entropy source) runs, and the plan line follows the last
+//! of them. Failing points are followed by `#` diagnostic lines describing
+//! the failure.
+//!
+//! <https://testanything.org/tap-specification.html>
+
+/// Test point writer, tracking how many points have been emitted.
+#[derive(Debug, Default)]
+pub struct Tap {
+    tests: u64,
+    failed: u64,
+}
+
+impl Tap {
+    /// Print the next test point, followed by one diagnostic line per entry
+    /// of `diagnostics`.
+    pub fn result(&mut self, ok: bool, description: &str, diagnostics: &[String]) {
+        self.tests += 1;
+        if !ok {
+            self.failed += 1;
+        }
+
+        println!(
+            "{}ok {} - {}",
+            if ok { "" } else { "not " },
+            self.tests,
+            escape(description)
+        );
+        for line in diagnostics {
+            println!("#   {line}");
+        }
+    }
+
+    /// Print the plan line covering every test point emitted so far.
+    pub fn plan(&self) {
+        println!("1..{}", self.tests);
+    }
+
+    /// Number of `not ok` test points emitted so far.
+    pub fn failed(&self) -> u64 {
+        self.failed
+    }
+}
+
+/// Escape `#` in a description, which would otherwise start a directive,
+/// and keep it on one line.
+fn escape(description: &str) -> String {
+    let mut out = String::with_capacity(description.len());
+    for c in description.chars() {
+        match c {
+            '#' => out.push_str("\\#"),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out
+}
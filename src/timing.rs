@@ -0,0 +1,46 @@
+//! Throughput measurement for `--timing`.
+
+use std::time::Duration;
+
+/// Time spent on a run's trials.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Timing {
+    /// Trials measured.
+    pub trials: u64,
+
+    /// Wall-clock time from the first trial starting to the last finishing.
+    pub wall: Duration,
+
+    /// Time spent signing, summed over every trial.
+    pub sign: Duration,
+
+    /// Time spent recovering, summed over every trial.
+    pub recover: Duration,
+}
+
+impl Timing {
+    /// Trials completed per second of wall-clock time.
+    pub fn trials_per_second(&self) -> f64 {
+        self.trials as f64 / self.wall.as_secs_f64()
+    }
+
+    /// Mean wall-clock time per trial, in microseconds. With several
+    /// threads this is the inverse of the throughput, not a trial's latency.
+    pub fn micros_per_trial(&self) -> f64 {
+        micros_per(self.wall, self.trials)
+    }
+
+    /// Mean time spent signing per trial, in microseconds.
+    pub fn sign_micros(&self) -> f64 {
+        micros_per(self.sign, self.trials)
+    }
+
+    /// Mean time spent recovering per trial, in microseconds.
+    pub fn recover_micros(&self) -> f64 {
+        micros_per(self.recover, self.trials)
+    }
+}
+
+fn micros_per(total: Duration, trials: u64) -> f64 {
+    total.as_secs_f64() * 1e6 / trials.max(1) as f64
+}